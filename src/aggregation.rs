@@ -0,0 +1,84 @@
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::ops::Range;
+
+/// Position of the aggregation window relative to the cursor.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum Alignment {
+    #[default]
+    Centered,
+    /// the window ends at the cursor
+    Trailing,
+    /// the window starts at the cursor
+    Leading,
+}
+
+/// Time slice around the cursor whose links are drawn in the graph.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Aggregation {
+    /// fixed width, or `None` to follow the zoom (1% of the visible window)
+    pub width: Option<u64>,
+    pub alignment: Alignment,
+}
+
+impl Aggregation {
+    pub fn width(&self, time_window: &Range<u64>) -> u64 {
+        self.width
+            .unwrap_or((time_window.end - time_window.start) / 100)
+            .max(1)
+    }
+
+    pub fn window_at(&self, t: u64, time_window: &Range<u64>) -> Range<u64> {
+        let dt = self.width(time_window);
+        match self.alignment {
+            Alignment::Centered => t.saturating_sub(dt / 2)..t.saturating_sub(dt / 2) + dt,
+            Alignment::Trailing => t.saturating_sub(dt)..t.saturating_sub(dt) + dt,
+            Alignment::Leading => t..t + dt,
+        }
+    }
+}
+
+#[component]
+pub fn AggregationControl(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    mut aggregation: Signal<Aggregation>,
+    time_window: ReadOnlySignal<Range<u64>>,
+) -> Element {
+    let format = current_dataset.read().time_format();
+    let Range { start, end } = current_dataset.read().time_window();
+    let widths: Vec<u64> = format
+        .nice_durations()
+        .take_while(|d| *d < end - start)
+        .collect();
+    let current = *aggregation.read();
+
+    rsx! {
+        div { class: "style-select",
+            span { "width" }
+            select {
+                onchange: move |e: Event<FormData>| aggregation.write().width = e.value().parse().ok(),
+                option { value: "", selected: current.width.is_none(),
+                    "auto ({format.format_duration(current.width(&time_window()))})"
+                }
+                for w in widths {
+                    option { value: "{w}", selected: current.width == Some(w), "{format.format_duration(w)}" }
+                }
+            }
+        }
+        div { class: "style-select",
+            span { "alignment" }
+            select {
+                onchange: move |e: Event<FormData>| {
+                    aggregation.write().alignment = match e.value().as_str() {
+                        "trailing" => Alignment::Trailing,
+                        "leading" => Alignment::Leading,
+                        _ => Alignment::Centered,
+                    }
+                },
+                option { value: "centered", selected: current.alignment == Alignment::Centered, "centered" }
+                option { value: "trailing", selected: current.alignment == Alignment::Trailing, "trailing" }
+                option { value: "leading", selected: current.alignment == Alignment::Leading, "leading" }
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+//! Centralities of the aggregated graph of a window, from its weighted
+//! adjacency matrix. Self loops are ignored.
+
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::collections::VecDeque;
+
+const ITERATIONS: usize = 100;
+/// largest change of a value at which the eigenvector iteration stops
+const TOLERANCE: f64 = 1e-9;
+const MAX_ITERATIONS: usize = 10_000;
+const DAMPING: f64 = 0.85;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Centrality {
+    /// number of neighbours
+    Degree,
+    /// total interaction time with the neighbours
+    Strength,
+    Eigenvector,
+    PageRank,
+    /// fraction of shortest paths (in hops) going through the node
+    Betweenness,
+    /// largest k such that the node is in a subgraph of minimum degree k
+    KCore,
+}
+
+impl Centrality {
+    pub const ALL: [Centrality; 6] = [
+        Centrality::Degree,
+        Centrality::Strength,
+        Centrality::Eigenvector,
+        Centrality::PageRank,
+        Centrality::Betweenness,
+        Centrality::KCore,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Centrality::Degree => "degree",
+            Centrality::Strength => "strength",
+            Centrality::Eigenvector => "eigenvector",
+            Centrality::PageRank => "PageRank",
+            Centrality::Betweenness => "betweenness",
+            Centrality::KCore => "k-core",
+        }
+    }
+
+    pub fn compute(&self, matrix: &[Vec<f64>]) -> Vec<f64> {
+        match self {
+            Centrality::Degree => neighbours(matrix).iter().map(|x| x.len() as f64).collect(),
+            Centrality::Strength => strength(matrix),
+            Centrality::Eigenvector => eigenvector(matrix),
+            Centrality::PageRank => pagerank(matrix),
+            Centrality::Betweenness => betweenness(matrix),
+            Centrality::KCore => core_numbers(matrix),
+        }
+    }
+}
+
+/// Values of every centrality, in the order of `Centrality::ALL`.
+pub fn all_centralities(matrix: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    Centrality::ALL.iter().map(|c| c.compute(matrix)).collect()
+}
+
+fn neighbours(matrix: &[Vec<f64>]) -> Vec<Vec<usize>> {
+    matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| (0..row.len()).filter(|j| *j != i && row[*j] > 0.).collect())
+        .collect()
+}
+
+fn strength(matrix: &[Vec<f64>]) -> Vec<f64> {
+    matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, w)| w)
+                .sum()
+        })
+        .collect()
+}
+
+/// Power iteration on `A + sI`, `s` being the largest strength. It has the
+/// same leading eigenvector as `A`, and as every eigenvalue of `A` is at
+/// least `-s`, it does not oscillate on bipartite graphs. The largest value is 1.
+fn eigenvector(matrix: &[Vec<f64>]) -> Vec<f64> {
+    let n = matrix.len();
+    let shift = strength(matrix).into_iter().fold(1., f64::max);
+    let mut x = vec![1.; n];
+    for _ in 0..MAX_ITERATIONS {
+        let mut next: Vec<f64> = (0..n)
+            .map(|i| {
+                shift * x[i]
+                    + (0..n)
+                        .filter(|j| *j != i)
+                        .map(|j| matrix[i][j] * x[j])
+                        .sum::<f64>()
+            })
+            .collect();
+        let max = next.iter().copied().fold(f64::MIN_POSITIVE, f64::max);
+        next.iter_mut().for_each(|v| *v /= max);
+        let change = x
+            .iter()
+            .zip(&next)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0., f64::max);
+        x = next;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+    x
+}
+
+/// Random walk following links in proportion to their weight, the walker
+/// of an isolated node jumping to any node.
+fn pagerank(matrix: &[Vec<f64>]) -> Vec<f64> {
+    let n = matrix.len();
+    let strength = strength(matrix);
+    let mut x = vec![1. / n as f64; n];
+    for _ in 0..ITERATIONS {
+        let dangling: f64 = (0..n).filter(|i| strength[*i] == 0.).map(|i| x[i]).sum();
+        let base = (1. - DAMPING) / n as f64 + DAMPING * dangling / n as f64;
+        x = (0..n)
+            .map(|i| {
+                base + DAMPING
+                    * (0..n)
+                        .filter(|j| *j != i && strength[*j] > 0.)
+                        .map(|j| x[j] * matrix[j][i] / strength[j])
+                        .sum::<f64>()
+            })
+            .collect();
+    }
+    x
+}
+
+/// Brandes' algorithm, normalized by the number of pairs of other nodes.
+fn betweenness(matrix: &[Vec<f64>]) -> Vec<f64> {
+    let n = matrix.len();
+    let neighbours = neighbours(matrix);
+    let mut result = vec![0.; n];
+    for s in 0..n {
+        let mut order = Vec::new();
+        let mut predecessors = vec![Vec::new(); n];
+        let mut paths = vec![0.; n];
+        let mut distance: Vec<Option<usize>> = vec![None; n];
+        paths[s] = 1.;
+        distance[s] = Some(0);
+        let mut queue = VecDeque::from([s]);
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            let d = distance[v].unwrap();
+            for &w in &neighbours[v] {
+                if distance[w].is_none() {
+                    distance[w] = Some(d + 1);
+                    queue.push_back(w);
+                }
+                if distance[w] == Some(d + 1) {
+                    paths[w] += paths[v];
+                    predecessors[w].push(v);
+                }
+            }
+        }
+        let mut dependency = vec![0.; n];
+        for &w in order.iter().rev() {
+            for &v in &predecessors[w] {
+                dependency[v] += paths[v] / paths[w] * (1. + dependency[w]);
+            }
+            if w != s {
+                result[w] += dependency[w];
+            }
+        }
+    }
+    // each pair was counted from both ends
+    let pairs = (n.saturating_sub(1) * n.saturating_sub(2)).max(1) as f64;
+    result.iter().map(|x| x / pairs).collect()
+}
+
+/// Batagelj and Zaversnik: nodes are removed by increasing degree.
+fn core_numbers(matrix: &[Vec<f64>]) -> Vec<f64> {
+    let neighbours = neighbours(matrix);
+    let n = neighbours.len();
+    let mut degree: Vec<usize> = neighbours.iter().map(|x| x.len()).collect();
+    let mut removed = vec![false; n];
+    let mut core = vec![0.; n];
+    let mut k = 0;
+    for _ in 0..n {
+        let v = (0..n)
+            .filter(|v| !removed[*v])
+            .min_by_key(|v| degree[*v])
+            .unwrap();
+        k = k.max(degree[v]);
+        core[v] = k as f64;
+        removed[v] = true;
+        for &w in &neighbours[v] {
+            if !removed[w] {
+                degree[w] -= 1;
+            }
+        }
+    }
+    core
+}
+
+/// Every centrality of every node, see `all_centralities`,
+/// sorted by the clicked column.
+#[component]
+pub fn CentralityTable(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    values: ReadOnlySignal<Vec<Vec<f64>>>,
+    hovered: Signal<Vec<usize>>,
+) -> Element {
+    let mut sort_by = use_signal(|| Centrality::Degree);
+
+    let dataset = current_dataset.read();
+    let names: Vec<&str> = dataset.node_names().collect();
+    let values = values();
+    let column = Centrality::ALL
+        .iter()
+        .position(|c| *c == sort_by())
+        .unwrap();
+    let mut rows: Vec<usize> = (0..names.len()).collect();
+    rows.sort_by(|a, b| values[column][*b].total_cmp(&values[column][*a]));
+
+    rsx! {
+        table { class: "centrality-table",
+            tr {
+                th { "node" }
+                for c in Centrality::ALL {
+                    th {
+                        class: if c == sort_by() { "sortable sorted" } else { "sortable" },
+                        onclick: move |_| sort_by.set(c),
+                        "{c.label()}"
+                    }
+                }
+            }
+            for i in rows {
+                tr {
+                    class: if hovered.read().contains(&i) { "centrality-row highlighted" } else { "centrality-row" },
+                    onmouseenter: move |_| hovered.set(vec![i]),
+                    onmouseleave: move |_| hovered.set(vec![]),
+                    td { "{names[i]}" }
+                    for v in values.iter().map(|column| column[i]) {
+                        td { "{v:.3}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Matrix;
+
+    fn graph(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<f64>> {
+        let mut m = vec![vec![0.; n]; n];
+        for (a, b) in edges {
+            m[*a][*b] = 1.;
+            m[*b][*a] = 1.;
+        }
+        m
+    }
+
+    fn assert_close(values: &[f64], expected: &[f64]) {
+        assert_eq!(values.len(), expected.len());
+        for (v, e) in values.iter().zip(expected) {
+            assert!((v - e).abs() < 1e-6, "{values:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn path() {
+        let m = graph(3, &[(0, 1), (1, 2)]);
+        assert_close(&Centrality::Degree.compute(&m), &[1., 2., 1.]);
+        assert_close(&betweenness(&m), &[0., 1., 0.]);
+        assert_close(&core_numbers(&m), &[1., 1., 1.]);
+        // leading eigenvector (1, √2, 1)
+        let r = 1. / 2f64.sqrt();
+        assert_close(&eigenvector(&m), &[r, 1., r]);
+        // x_0 = 0.05 + 0.85 x_1 / 2 and x_1 = 0.05 + 0.85 * 2 x_0
+        let end = 0.07125 / 0.2775;
+        assert_close(&pagerank(&m), &[end, 0.05 + 1.7 * end, end]);
+    }
+
+    #[test]
+    fn star() {
+        let m = graph(4, &[(0, 1), (0, 2), (0, 3)]);
+        assert_close(&Centrality::Degree.compute(&m), &[3., 1., 1., 1.]);
+        assert_close(&betweenness(&m), &[1., 0., 0., 0.]);
+        assert_close(&core_numbers(&m), &[1., 1., 1., 1.]);
+        // leading eigenvector (√3, 1, 1, 1)
+        let r = 1. / 3f64.sqrt();
+        assert_close(&eigenvector(&m), &[1., r, r, r]);
+    }
+
+    #[test]
+    fn weighted_star() {
+        let m = graph(4, &[(0, 1), (0, 2), (0, 3)]).matrix_map(|w| 1000. * w);
+        let r = 1. / 3f64.sqrt();
+        assert_close(&eigenvector(&m), &[1., r, r, r]);
+    }
+
+    #[test]
+    fn triangle_with_a_pendant_node() {
+        let mut m = graph(4, &[(0, 1), (1, 2), (0, 2), (2, 3)]);
+        m[3][3] = 5.;
+        assert_close(&strength(&m), &[2., 2., 3., 1.]);
+        // 3 goes through 2 to reach 0 and 1
+        assert_close(&betweenness(&m), &[0., 0., 2. / 3., 0.]);
+        assert_close(&core_numbers(&m), &[2., 2., 2., 1.]);
+        let rank = pagerank(&m);
+        assert!((rank.iter().sum::<f64>() - 1.).abs() < 1e-9);
+        assert!(rank[2] > rank[0] && rank[0] > rank[3]);
+    }
+}
@@ -0,0 +1,318 @@
+//! Maximal Δ-cliques, from "Computing maximal cliques in link streams"
+//! (Viard, Latapy & Magnien, 2016).
+//!
+//! `(X, [b, e])` is a Δ-clique if every pair of nodes of `X` is linked at
+//! least once during every sub-interval of `[b, e]` of duration `Δ`.
+//! A link present during `[start, end]` counts as linked at every time of it.
+
+use crate::graph_data::Highlight;
+use crate::timeline_brush::clamp_window;
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// Closed time interval.
+type Interval = (u64, u64);
+
+/// number of candidate cliques examined before the search gives up,
+/// as the search is exponential in the size of the cliques
+const MAX_EXPANSIONS: usize = 100_000;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct DeltaClique {
+    /// sorted
+    pub nodes: Vec<usize>,
+    pub start: u64,
+    pub end: u64,
+}
+
+fn intersection(a: &[Interval], b: &[Interval]) -> Vec<Interval> {
+    let (mut i, mut j) = (0, 0);
+    let mut result = Vec::new();
+    while i < a.len() && j < b.len() {
+        let start = a[i].0.max(b[j].0);
+        let end = a[i].1.min(b[j].1);
+        if start <= end {
+            result.push((start, end));
+        }
+        if a[i].1 < b[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+struct Search {
+    delta: u64,
+    min_size: usize,
+    /// for each linked pair `u < v`, the times `τ` such that `u` and `v`
+    /// are linked during `[τ, τ + Δ]`
+    coverage: HashMap<(usize, usize), Vec<Interval>>,
+    neighbours: Vec<Vec<usize>>,
+    visited: HashSet<(Vec<usize>, Interval)>,
+    result: Vec<DeltaClique>,
+}
+
+/// Result of a search, possibly stopped before the end.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Cliques {
+    pub cliques: Vec<DeltaClique>,
+    /// the search stopped after `MAX_EXPANSIONS` candidates, some cliques are missing
+    pub truncated: bool,
+}
+
+impl Search {
+    fn coverage(&self, u: usize, v: usize) -> &[Interval] {
+        self.coverage
+            .get(&(u.min(v), u.max(v)))
+            .map_or(&[], |x| x.as_slice())
+    }
+
+    /// `interval` is a maximal interval of times `τ` at which all the pairs
+    /// of `nodes` are covered, so only the set of nodes may be extended.
+    fn expand(&mut self, nodes: Vec<usize>, interval: Interval) {
+        if self.visited.len() >= MAX_EXPANSIONS || !self.visited.insert((nodes.clone(), interval)) {
+            return;
+        }
+        let candidates: Vec<usize> = self.neighbours[nodes[0]]
+            .iter()
+            .copied()
+            .filter(|w| !nodes.contains(w))
+            .collect();
+
+        let mut maximal = true;
+        for w in candidates {
+            let mut times = vec![interval];
+            for x in &nodes {
+                times = intersection(&times, self.coverage(*x, w));
+                if times.is_empty() {
+                    break;
+                }
+            }
+            for t in times {
+                maximal &= t != interval;
+                let mut next = nodes.clone();
+                next.push(w);
+                next.sort();
+                self.expand(next, t);
+            }
+        }
+
+        if maximal && nodes.len() >= self.min_size {
+            self.result.push(DeltaClique {
+                nodes,
+                start: interval.0,
+                end: interval.1 + self.delta,
+            });
+        }
+    }
+}
+
+/// Every maximal Δ-clique with at least `min_size` nodes, sorted by start time.
+pub fn delta_cliques(stream: &LinkStream, delta: u64, min_size: usize) -> Cliques {
+    let mut links: HashMap<(usize, usize), Vec<Interval>> = HashMap::new();
+    for link in stream.links_during(stream.time_window()) {
+        if link.n1 != link.n2 {
+            let key = (link.n1.min(link.n2), link.n1.max(link.n2));
+            links.entry(key).or_default().push((link.start, link.end));
+        }
+    }
+
+    // links separated by at most Δ cover the time between them
+    let mut coverage = HashMap::new();
+    let mut neighbours = vec![Vec::new(); stream.node_count()];
+    for ((u, v), mut times) in links {
+        times.sort();
+        let mut blocks: Vec<Interval> = Vec::new();
+        for (start, end) in times {
+            match blocks.last_mut() {
+                Some(last) if start <= last.1 + delta => last.1 = last.1.max(end),
+                _ => blocks.push((start, end)),
+            }
+        }
+        let covered = blocks
+            .into_iter()
+            .map(|(start, end)| (start.saturating_sub(delta), end))
+            .collect();
+        coverage.insert((u, v), covered);
+        neighbours[u].push(v);
+        neighbours[v].push(u);
+    }
+
+    let mut search = Search {
+        delta,
+        min_size,
+        coverage,
+        neighbours,
+        visited: HashSet::new(),
+        result: Vec::new(),
+    };
+    let mut pairs: Vec<((usize, usize), Vec<Interval>)> = search
+        .coverage
+        .iter()
+        .map(|(k, v)| (*k, v.clone()))
+        .collect();
+    pairs.sort();
+    for ((u, v), times) in pairs {
+        for t in times {
+            search.expand(vec![u, v], t);
+        }
+    }
+
+    let truncated = search.visited.len() >= MAX_EXPANSIONS;
+    let mut cliques = search.result;
+    cliques.sort_by(|a, b| (a.start, &a.nodes).cmp(&(b.start, &b.nodes)));
+    Cliques { cliques, truncated }
+}
+
+/// Members of a clique and the links between them.
+pub fn clique_highlight(clique: &DeltaClique) -> Highlight {
+    let mut highlight = Highlight::default();
+    for (i, u) in clique.nodes.iter().enumerate() {
+        highlight.node_classes.push((*u, "clique".to_string()));
+        for v in &clique.nodes[i + 1..] {
+            highlight.edges.push((*u, *v));
+        }
+    }
+    highlight
+}
+
+/// Δ and minimum size choice, and the table of the cliques of the dataset.
+/// A click on a clique shows its interval and highlights its members.
+#[component]
+pub fn CliquePanel(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    mut time_window: Signal<Range<u64>>,
+    mut focused: Signal<Option<DeltaClique>>,
+) -> Element {
+    const MAX_ROWS: usize = 100;
+    let mut delta = use_signal(|| 0u64);
+    let mut min_size = use_signal(|| 3usize);
+    let cliques = use_memo(move || delta_cliques(&current_dataset.read(), delta(), min_size()));
+
+    let dataset = current_dataset.read();
+    let format = dataset.time_format();
+    let names: Vec<&str> = dataset.node_names().collect();
+    let Range { start, end } = dataset.time_window();
+    let deltas: Vec<u64> = std::iter::once(0)
+        .chain(format.nice_durations().take_while(|d| *d < end - start))
+        .collect();
+    let count = cliques.read().cliques.len();
+    let truncated = cliques.read().truncated;
+
+    rsx! {
+        div { class: "style-select",
+            span { "Δ" }
+            select {
+                onchange: move |e: Event<FormData>| delta.set(e.value().parse().unwrap_or(0)),
+                for d in deltas {
+                    option { value: "{d}", selected: d == delta(), "{format.format_duration(d)}" }
+                }
+            }
+        }
+        div { class: "style-select",
+            span { "min size" }
+            input {
+                r#type: "number",
+                min: "2",
+                value: "{min_size}",
+                oninput: move |e| min_size.set(e.value().parse::<usize>().unwrap_or(2).max(2))
+            }
+        }
+        div { class: "data-output",
+            if truncated {
+                p { "search stopped, some cliques are missing: try a smaller Δ or a larger min size" }
+            }
+            if count > MAX_ROWS {
+                p { "{count} cliques, first {MAX_ROWS} shown" }
+            } else {
+                p { "{count} cliques" }
+            }
+        }
+        table { class: "clique-table",
+            tr {
+                th { "members" }
+                th { "from" }
+                th { "duration" }
+            }
+            for clique in cliques.read().cliques.iter().take(MAX_ROWS).cloned() {
+                tr {
+                    class: if focused.read().as_ref() == Some(&clique) { "clique-row focused" } else { "clique-row" },
+                    onclick: {
+                        let clique = clique.clone();
+                        move |_| {
+                            let bounds = current_dataset.read().time_window();
+                            time_window.set(clamp_window(clique.start as f64..clique.end as f64, bounds));
+                            focused.set(Some(clique.clone()));
+                        }
+                    },
+                    td {
+                        {clique.nodes.iter().filter_map(|u| names.get(*u)).copied().collect::<Vec<_>>().join(", ")}
+                    }
+                    td { "{format.format_time(clique.start)}" }
+                    td { "{format.format_duration(clique.end - clique.start)}" }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linkstream::test_stream;
+
+    // a-b and b-c during [0, 10], a-c during [5, 10],
+    // c-d during [20, 22] and [30, 32]
+    fn small_stream() -> LinkStream {
+        test_stream(
+            &["a", "b", "c", "d"],
+            &[
+                (0, 1, 0, 10),
+                (1, 2, 0, 10),
+                (0, 2, 5, 10),
+                (2, 3, 20, 22),
+                (2, 3, 30, 32),
+            ],
+            0..40,
+        )
+    }
+
+    fn summary(cliques: &Cliques) -> Vec<(Vec<usize>, u64, u64)> {
+        cliques
+            .cliques
+            .iter()
+            .map(|c| (c.nodes.clone(), c.start, c.end))
+            .collect()
+    }
+
+    #[test]
+    fn cliques_without_delta() {
+        let cliques = delta_cliques(&small_stream(), 0, 2);
+        assert!(!cliques.truncated);
+        assert_eq!(
+            summary(&cliques),
+            vec![
+                (vec![0, 1], 0, 10),
+                (vec![1, 2], 0, 10),
+                (vec![0, 1, 2], 5, 10),
+                (vec![2, 3], 20, 22),
+                (vec![2, 3], 30, 32),
+            ]
+        );
+        assert_eq!(
+            summary(&delta_cliques(&small_stream(), 0, 3)),
+            vec![(vec![0, 1, 2], 5, 10)]
+        );
+    }
+
+    #[test]
+    fn links_closer_than_delta_make_one_clique() {
+        let cliques = delta_cliques(&small_stream(), 10, 2);
+        // every interval of duration 10 in [10, 42] contains a c-d link
+        assert!(summary(&cliques).contains(&(vec![2, 3], 10, 42)));
+    }
+}
@@ -0,0 +1,345 @@
+use crate::LinkStream;
+use std::cmp::Reverse;
+use std::ops::Range;
+
+/// Community of each node, by label propagation on a weighted adjacency matrix.
+/// Labels are renumbered from 0 in order of first appearance.
+pub fn label_propagation(matrix: &[Vec<f64>]) -> Vec<usize> {
+    let n = matrix.len();
+    let mut labels: Vec<usize> = (0..n).collect();
+
+    for _ in 0..100 {
+        let mut changed = false;
+        for i in 0..n {
+            let mut scores = vec![0.; n];
+            for (j, w) in matrix[i].iter().enumerate() {
+                if j != i {
+                    scores[labels[j]] += w;
+                }
+            }
+            // ties are broken by the smallest label
+            let (best, score) = scores
+                .iter()
+                .enumerate()
+                .fold(
+                    (labels[i], 0.),
+                    |acc, (l, s)| if *s > acc.1 { (l, *s) } else { acc },
+                );
+            if score > 0. && best != labels[i] {
+                labels[i] = best;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    renumber(&labels)
+}
+
+/// Renumber labels from 0, in order of first appearance.
+pub fn renumber(labels: &[usize]) -> Vec<usize> {
+    let mut seen: Vec<usize> = Vec::new();
+    labels
+        .iter()
+        .map(|l| match seen.iter().position(|x| x == l) {
+            Some(i) => i,
+            None => {
+                seen.push(*l);
+                seen.len() - 1
+            }
+        })
+        .collect()
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum CommunityMethod {
+    #[default]
+    Louvain,
+    Leiden,
+    LabelPropagation,
+}
+
+impl CommunityMethod {
+    pub const ALL: [CommunityMethod; 3] = [
+        CommunityMethod::Louvain,
+        CommunityMethod::Leiden,
+        CommunityMethod::LabelPropagation,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CommunityMethod::Louvain => "louvain",
+            CommunityMethod::Leiden => "leiden",
+            CommunityMethod::LabelPropagation => "label propagation",
+        }
+    }
+
+    pub fn detect(&self, matrix: &[Vec<f64>]) -> Vec<usize> {
+        match self {
+            CommunityMethod::Louvain => louvain(matrix),
+            CommunityMethod::Leiden => leiden(matrix),
+            CommunityMethod::LabelPropagation => label_propagation(matrix),
+        }
+    }
+}
+
+fn count(labels: &[usize]) -> usize {
+    labels.iter().max().map_or(0, |x| x + 1)
+}
+
+/// Move single nodes between communities while the modularity increases,
+/// starting from `community`, whose labels are smaller than the number of nodes.
+/// Returns the renumbered communities and whether a node moved.
+fn local_moving(matrix: &[Vec<f64>], mut community: Vec<usize>) -> (Vec<usize>, bool) {
+    let n = matrix.len();
+    let degrees: Vec<f64> = matrix.iter().map(|row| row.iter().sum()).collect();
+    let m2: f64 = degrees.iter().sum();
+    let mut total = vec![0.; n];
+    for (c, d) in community.iter().zip(&degrees) {
+        total[*c] += d;
+    }
+    let mut moved = false;
+    if m2 == 0. {
+        return (community, false);
+    }
+
+    loop {
+        let mut changed = false;
+        for i in 0..n {
+            let old = community[i];
+            total[old] -= degrees[i];
+
+            let mut links = vec![0.; n];
+            for (j, w) in matrix[i].iter().enumerate() {
+                if j != i && *w > 0. {
+                    links[community[j]] += w;
+                }
+            }
+            let gain = |c: usize| links[c] - total[c] * degrees[i] / m2;
+            let mut best = old;
+            for (c, l) in links.iter().enumerate() {
+                if *l > 0. && gain(c) > gain(best) + 1e-12 {
+                    best = c;
+                }
+            }
+
+            total[best] += degrees[i];
+            if best != old {
+                community[i] = best;
+                changed = true;
+                moved = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    (renumber(&community), moved)
+}
+
+/// Refinement phase of the Leiden method: inside each community, nodes still
+/// alone and well connected to their community are merged into the well
+/// connected sub-community that increases the modularity the most (the
+/// original method picks one at random among the increasing ones).
+/// Sub-communities are connected, and together they make up `community`.
+fn refine(matrix: &[Vec<f64>], community: &[usize]) -> Vec<usize> {
+    let n = matrix.len();
+    let degrees: Vec<f64> = matrix.iter().map(|row| row.iter().sum()).collect();
+    let m2: f64 = degrees.iter().sum();
+    let mut refined: Vec<usize> = (0..n).collect();
+    if m2 == 0. {
+        return refined;
+    }
+    let mut community_total = vec![0.; count(community)];
+    for (c, d) in community.iter().zip(&degrees) {
+        community_total[*c] += d;
+    }
+    // degree, size, and weight towards the rest of the community, of each sub-community
+    let mut total = degrees.clone();
+    let mut size = vec![1; n];
+    let mut outside: Vec<f64> = (0..n)
+        .map(|i| {
+            (0..n)
+                .filter(|j| *j != i && community[*j] == community[i])
+                .map(|j| matrix[i][j])
+                .sum()
+        })
+        .collect();
+    let well_connected =
+        |outside: f64, total: f64, c: usize| outside >= total * (community_total[c] - total) / m2;
+
+    for v in 0..n {
+        let c = community[v];
+        if size[v] != 1 || refined[v] != v || !well_connected(outside[v], degrees[v], c) {
+            continue;
+        }
+        let mut links = vec![0.; n];
+        for (j, w) in matrix[v].iter().enumerate() {
+            if j != v && *w > 0. && community[j] == c {
+                links[refined[j]] += w;
+            }
+        }
+        let gain = |r: usize| links[r] - total[r] * degrees[v] / m2;
+        let best = (0..n)
+            .filter(|r| links[*r] > 0. && well_connected(outside[*r], total[*r], c))
+            .filter(|r| gain(*r) >= 0.)
+            .max_by(|a, b| gain(*a).total_cmp(&gain(*b)));
+        if let Some(r) = best {
+            outside[r] += outside[v] - 2. * links[r];
+            total[r] += degrees[v];
+            size[r] += 1;
+            size[v] = 0;
+            refined[v] = r;
+        }
+    }
+    renumber(&refined)
+}
+
+/// Graph whose nodes are the communities.
+fn aggregate(matrix: &[Vec<f64>], community: &[usize]) -> Vec<Vec<f64>> {
+    let k = community.iter().max().map_or(0, |x| x + 1);
+    let mut result = vec![vec![0.; k]; k];
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, w) in row.iter().enumerate() {
+            result[community[i]][community[j]] += w;
+        }
+    }
+    result
+}
+
+/// Modularity optimisation by the Louvain method.
+pub fn louvain(matrix: &[Vec<f64>]) -> Vec<usize> {
+    let mut membership: Vec<usize> = (0..matrix.len()).collect();
+    let mut graph = matrix.to_vec();
+    loop {
+        let (community, moved) = local_moving(&graph, (0..graph.len()).collect());
+        if !moved {
+            break;
+        }
+        for m in membership.iter_mut() {
+            *m = community[*m];
+        }
+        graph = aggregate(&graph, &community);
+    }
+    renumber(&membership)
+}
+
+/// Modularity optimisation by the Leiden method (Traag et al., 2019): the
+/// graph is aggregated by refined communities, which start the next level
+/// in the community they were in, so that no community is disconnected.
+pub fn leiden(matrix: &[Vec<f64>]) -> Vec<usize> {
+    let mut membership: Vec<usize> = (0..matrix.len()).collect();
+    let mut graph = matrix.to_vec();
+    let mut initial: Vec<usize> = (0..graph.len()).collect();
+    loop {
+        let (community, _) = local_moving(&graph, initial);
+        let refined = refine(&graph, &community);
+        // stop when nothing can be aggregated any more
+        if count(&community) == graph.len() || count(&refined) == graph.len() {
+            let result: Vec<usize> = membership.iter().map(|m| community[*m]).collect();
+            return renumber(&result);
+        }
+        initial = vec![0; count(&refined)];
+        for (r, c) in refined.iter().zip(&community) {
+            initial[*r] = *c;
+        }
+        for m in membership.iter_mut() {
+            *m = refined[*m];
+        }
+        graph = aggregate(&graph, &refined);
+    }
+}
+
+/// Relabel `current` so that its communities keep the label of the
+/// community of `previous` they overlap the most.
+/// Communities without a match take the smallest unused labels.
+pub fn match_labels(previous: &[usize], current: &[usize]) -> Vec<usize> {
+    let k = current.iter().max().map_or(0, |x| x + 1);
+    let p = previous.iter().max().map_or(0, |x| x + 1);
+    let mut overlaps = Vec::new();
+    for c in 0..k {
+        for q in 0..p {
+            let overlap = current
+                .iter()
+                .zip(previous)
+                .filter(|(a, b)| **a == c && **b == q)
+                .count();
+            if overlap > 0 {
+                overlaps.push((overlap, c, q));
+            }
+        }
+    }
+    overlaps.sort_by_key(|o| Reverse(o.0));
+
+    let mut label: Vec<Option<usize>> = vec![None; k];
+    let mut used = vec![false; p.max(k) + k];
+    for (_, c, q) in overlaps {
+        if label[c].is_none() && !used[q] {
+            label[c] = Some(q);
+            used[q] = true;
+        }
+    }
+    for l in label.iter_mut().filter(|l| l.is_none()) {
+        let free = used.iter().position(|u| !u).unwrap();
+        *l = Some(free);
+        used[free] = true;
+    }
+    current.iter().map(|c| label[*c].unwrap()).collect()
+}
+
+/// Communities of consecutive windows, with labels matched from one window to the next.
+pub fn temporal_communities(
+    stream: &LinkStream,
+    windows: &[Range<u64>],
+    method: CommunityMethod,
+) -> Vec<Vec<usize>> {
+    let mut result: Vec<Vec<usize>> = Vec::new();
+    for w in windows {
+        let current = method.detect(&stream.interaction_matrix(w.clone()));
+        let labels = match result.last() {
+            Some(previous) => match_labels(previous, &current),
+            None => current,
+        };
+        result.push(labels);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // two triangles 0-1-2 and 3-4-5 joined by the edge 2-3
+    fn two_triangles() -> Vec<Vec<f64>> {
+        let mut m = vec![vec![0.; 6]; 6];
+        for (a, b) in [(0, 1), (1, 2), (0, 2), (3, 4), (4, 5), (3, 5), (2, 3)] {
+            m[a][b] = 1.;
+            m[b][a] = 1.;
+        }
+        m
+    }
+
+    #[test]
+    fn methods_find_the_two_triangles() {
+        for method in [CommunityMethod::Louvain, CommunityMethod::Leiden] {
+            assert_eq!(
+                method.detect(&two_triangles()),
+                vec![0, 0, 0, 1, 1, 1],
+                "{method:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn refined_communities_are_connected() {
+        // one community made of the two triangles without the edge 2-3
+        let mut m = two_triangles();
+        m[2][3] = 0.;
+        m[3][2] = 0.;
+        let refined = refine(&m, &[0; 6]);
+        assert!(refined[..3].iter().all(|r| !refined[3..].contains(r)));
+        assert_eq!(leiden(&m), vec![0, 0, 0, 1, 1, 1]);
+    }
+}
@@ -0,0 +1,239 @@
+//! Connected components of aggregated graphs.
+
+use crate::aggregation::Aggregation;
+use crate::graph_data::Highlight;
+use crate::linkstream::Link;
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use std::ops::Range;
+
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    pub fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+    }
+}
+
+/// Component of each node of a weighted adjacency matrix, numbered from 0.
+pub fn connected_components(matrix: &[Vec<f64>]) -> Vec<usize> {
+    let n = matrix.len();
+    let mut uf = UnionFind::new(n);
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, w) in row.iter().enumerate() {
+            if *w > 0. {
+                uf.union(i, j);
+            }
+        }
+    }
+    let roots: Vec<usize> = (0..n).map(|i| uf.find(i)).collect();
+    crate::communities::renumber(&roots)
+}
+
+/// Components of the aggregated graph of one window.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ComponentCount {
+    pub time: u64,
+    /// number of components of the active nodes, linked to another node
+    /// during the window: isolated nodes are not components
+    pub count: usize,
+    /// number of nodes of the largest component
+    pub giant: usize,
+}
+
+fn count_components(uf: &mut UnionFind) -> (usize, usize) {
+    let n = uf.parent.len();
+    let roots: Vec<usize> = (0..n)
+        .filter(|i| uf.find(*i) == *i && uf.size[*i] > 1)
+        .collect();
+    let giant = roots.iter().map(|r| uf.size[*r]).max().unwrap_or(0);
+    (roots.len(), giant)
+}
+
+/// Components of the aggregated graph at each of `times`, the graph at `t`
+/// being made of the links present during `window_at(t)`.
+///
+/// `times` must be sorted, and the windows must move forward with them.
+/// Links entering a window are merged into the current components; the
+/// components are only rebuilt when a pair of nodes stops being linked.
+pub fn component_series(
+    stream: &LinkStream,
+    times: &[u64],
+    window_at: impl Fn(u64) -> Range<u64>,
+) -> Vec<ComponentCount> {
+    let (Some(first), Some(last)) = (times.first(), times.last()) else {
+        return Vec::new();
+    };
+    let span = window_at(*first).start..window_at(*last).end;
+    let mut entering: Vec<Link> = stream.links_during(span).filter(|l| l.n1 != l.n2).collect();
+    entering.sort_by_key(|l| l.start);
+    let mut leaving = entering.clone();
+    leaving.sort_by_key(|l| l.end);
+
+    let n = stream.node_count();
+    let mut uf = UnionFind::new(n);
+    let mut pairs: HashMap<(usize, usize), usize> = HashMap::new();
+    let (mut next_in, mut next_out) = (0, 0);
+    let mut result = Vec::new();
+
+    for t in times {
+        let window = window_at(*t);
+        while next_in < entering.len() && entering[next_in].start < window.end {
+            let Link { n1, n2, .. } = entering[next_in];
+            let count = pairs.entry((n1.min(n2), n1.max(n2))).or_default();
+            *count += 1;
+            if *count == 1 {
+                uf.union(n1, n2);
+            }
+            next_in += 1;
+        }
+        let mut removed = false;
+        while next_out < leaving.len() && leaving[next_out].end <= window.start {
+            let Link { n1, n2, .. } = leaving[next_out];
+            let key = (n1.min(n2), n1.max(n2));
+            if let Some(count) = pairs.get_mut(&key) {
+                *count -= 1;
+                if *count == 0 {
+                    pairs.remove(&key);
+                    removed = true;
+                }
+            }
+            next_out += 1;
+        }
+        if removed {
+            uf = UnionFind::new(n);
+            for (a, b) in pairs.keys() {
+                uf.union(*a, *b);
+            }
+        }
+        let (count, giant) = count_components(&mut uf);
+        result.push(ComponentCount {
+            time: *t,
+            count,
+            giant,
+        });
+    }
+    result
+}
+
+/// The component of `node` in the graph of a weighted adjacency matrix,
+/// with the edges inside it.
+pub fn component_highlight(matrix: &[Vec<f64>], node: usize) -> Highlight {
+    let components = connected_components(matrix);
+    let mut highlight = Highlight::default();
+    for (i, c) in components.iter().enumerate() {
+        let class = match c {
+            _ if i == node => "component-source",
+            c if *c == components[node] => "component",
+            _ => continue,
+        };
+        highlight.node_classes.push((i, class.to_string()));
+        for (j, w) in matrix[i].iter().enumerate() {
+            if *w > 0. && i < j {
+                highlight.edges.push((i, j));
+            }
+        }
+    }
+    highlight
+}
+
+/// Number of components and size of the largest one along the time window,
+/// both relative to the number of nodes.
+#[component]
+pub fn ComponentTimeLine(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    time_window: ReadOnlySignal<Range<u64>>,
+    aggregation: ReadOnlySignal<Aggregation>,
+) -> Element {
+    const N_POINTS: u64 = 100;
+    let series = use_memo(move || {
+        let Range { start, end } = time_window();
+        let step = ((end - start) / N_POINTS).max(1);
+        let times: Vec<u64> = (start..end).step_by(step as usize).collect();
+        let window = time_window();
+        component_series(&current_dataset.read(), &times, |t| {
+            aggregation().window_at(t, &window)
+        })
+    });
+
+    let n = current_dataset.read().node_count().max(1) as f64;
+    let Range { start, end } = time_window();
+    let x = |t: u64| (t - start) as f64 / (end - start).max(1) as f64 * 100.;
+    let y = |v: usize| 10. - 10. * v as f64 / n;
+    let line = |value: fn(&ComponentCount) -> usize| {
+        series
+            .read()
+            .iter()
+            .map(|p| format!("{},{}", x(p.time), y(value(p))))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    rsx! {
+        div { class: "component-timeline",
+            title: "number of components (blue), size of the largest one (orange)",
+            svg {
+                preserve_aspect_ratio: "none",
+                view_box: "0 0 100 10",
+                height: "100%",
+                width: "100%",
+                polyline { class: "component-count", points: line(|p| p.count) }
+                polyline { class: "giant-size", points: line(|p| p.giant) }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_stats::GraphStats;
+    use crate::linkstream::test_stream;
+
+    #[test]
+    fn stats_and_series_count_the_same_components() {
+        // a-b linked, c with a self loop, d isolated
+        let stream = test_stream(&["a", "b", "c", "d"], &[(0, 1, 0, 5), (2, 2, 0, 5)], 0..10);
+        let series = component_series(&stream, &[0], |_| 0..10);
+        assert_eq!(
+            series,
+            vec![ComponentCount {
+                time: 0,
+                count: 1,
+                giant: 2
+            }]
+        );
+        let stats = GraphStats::new(&stream, 0..10, 10);
+        assert_eq!((stats.components, stats.largest_component), (1, 2));
+        assert_eq!(stats.active_nodes, 2);
+        assert_eq!(stats.top_nodes, vec![(0, 5), (1, 5)]);
+    }
+}
@@ -0,0 +1,366 @@
+//! Distributions of contact durations and inter-contact times, with the
+//! burstiness and memory coefficients of "Burstiness and memory in complex
+//! systems" (Goh & Barabási, 2008).
+//!
+//! A contact is a maximal period during which a pair of nodes is linked:
+//! overlapping links of a pair, in either direction, make a single contact.
+
+use crate::linkstream::Link;
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// Contacts of every pair `u < v`, in time order.
+fn pair_contacts(links: &[Link]) -> BTreeMap<(usize, usize), Vec<Range<u64>>> {
+    let mut pairs: BTreeMap<(usize, usize), Vec<Range<u64>>> = BTreeMap::new();
+    for link in links.iter().filter(|l| l.n1 != l.n2) {
+        let key = (link.n1.min(link.n2), link.n1.max(link.n2));
+        pairs.entry(key).or_default().push(link.start..link.end);
+    }
+    for contacts in pairs.values_mut() {
+        contacts.sort_by_key(|c| c.start);
+        let mut merged: Vec<Range<u64>> = Vec::new();
+        for c in contacts.drain(..) {
+            match merged.last_mut() {
+                Some(last) if c.start <= last.end => last.end = last.end.max(c.end),
+                _ => merged.push(c),
+            }
+        }
+        *contacts = merged;
+    }
+    pairs
+}
+
+fn mean_std(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ContactTimes {
+    pub durations: Vec<u64>,
+    /// inter-contact times of each pair, in time order
+    pub gaps: Vec<Vec<u64>>,
+}
+
+impl ContactTimes {
+    fn from_contacts<'a>(pairs: impl Iterator<Item = &'a Vec<Range<u64>>>) -> Self {
+        let mut result = Self::default();
+        for contacts in pairs {
+            result
+                .durations
+                .extend(contacts.iter().map(|c| c.end - c.start));
+            result
+                .gaps
+                .push(contacts.windows(2).map(|w| w[1].start - w[0].end).collect());
+        }
+        result
+    }
+
+    /// Contacts of all the pairs of the dataset.
+    pub fn of_dataset(links: &[Link]) -> Self {
+        Self::from_contacts(pair_contacts(links).values())
+    }
+
+    /// Contacts between `u` and `v`.
+    pub fn of_pair(links: &[Link], u: usize, v: usize) -> Self {
+        let pair: Vec<Link> = links
+            .iter()
+            .filter(|l| (l.n1, l.n2) == (u, v) || (l.n1, l.n2) == (v, u))
+            .copied()
+            .collect();
+        Self::from_contacts(pair_contacts(&pair).values())
+    }
+
+    pub fn inter_contact_times(&self) -> Vec<u64> {
+        self.gaps.iter().flatten().copied().collect()
+    }
+
+    /// B = (σ - μ) / (σ + μ) of the inter-contact times: -1 for periodic
+    /// contacts, 0 for a Poisson process, close to 1 for bursts.
+    pub fn burstiness(&self) -> Option<f64> {
+        let gaps: Vec<f64> = self
+            .inter_contact_times()
+            .iter()
+            .map(|x| *x as f64)
+            .collect();
+        if gaps.len() < 2 {
+            return None;
+        }
+        let (mean, std) = mean_std(&gaps);
+        (mean + std > 0.).then(|| (std - mean) / (std + mean))
+    }
+
+    /// M, the correlation between consecutive inter-contact times of a pair.
+    pub fn memory(&self) -> Option<f64> {
+        let (first, second): (Vec<f64>, Vec<f64>) = self
+            .gaps
+            .iter()
+            .flat_map(|g| g.windows(2).map(|w| (w[0] as f64, w[1] as f64)))
+            .unzip();
+        if first.len() < 2 {
+            return None;
+        }
+        let (m1, s1) = mean_std(&first);
+        let (m2, s2) = mean_std(&second);
+        if s1 == 0. || s2 == 0. {
+            return None;
+        }
+        let covariance = first
+            .iter()
+            .zip(&second)
+            .map(|(a, b)| (a - m1) * (b - m2))
+            .sum::<f64>()
+            / first.len() as f64;
+        Some(covariance / (s1 * s2))
+    }
+}
+
+/// Bin of a histogram with logarithmic bins.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Bin {
+    pub start: f64,
+    pub end: f64,
+    /// fraction of the values in the bin, divided by its width
+    pub density: f64,
+}
+
+/// Histogram of the positive values, with `per_decade` bins for each power of ten.
+pub fn log_histogram(values: &[u64], per_decade: usize) -> Vec<Bin> {
+    let positive: Vec<f64> = values
+        .iter()
+        .filter(|x| **x > 0)
+        .map(|x| *x as f64)
+        .collect();
+    let Some(max) = positive.iter().copied().reduce(f64::max) else {
+        return Vec::new();
+    };
+    let min = positive.iter().copied().fold(f64::INFINITY, f64::min);
+    let step = 1. / per_decade as f64;
+    let first = (min.log10() / step).floor() as i32;
+    let last = (max.log10() / step).floor() as i32;
+
+    let n_bins = (last - first + 1) as usize;
+    let mut counts = vec![0; n_bins];
+    for x in &positive {
+        let k = ((x.log10() / step).floor() as i32 - first) as usize;
+        counts[k.min(n_bins - 1)] += 1;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .filter(|(_, c)| *c > 0)
+        .map(|(k, c)| {
+            let start = 10f64.powf((first + k as i32) as f64 * step);
+            let end = 10f64.powf((first + k as i32 + 1) as f64 * step);
+            Bin {
+                start,
+                end,
+                density: c as f64 / positive.len() as f64 / (end - start),
+            }
+        })
+        .collect()
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+enum Distribution {
+    #[default]
+    Durations,
+    InterContact,
+}
+
+impl Distribution {
+    const ALL: [Distribution; 2] = [Distribution::Durations, Distribution::InterContact];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Distribution::Durations => "contact durations",
+            Distribution::InterContact => "inter-contact times",
+        }
+    }
+
+    fn values(&self, times: &ContactTimes) -> Vec<u64> {
+        match self {
+            Distribution::Durations => times.durations.clone(),
+            Distribution::InterContact => times.inter_contact_times(),
+        }
+    }
+}
+
+/// Log-log histogram of the whole dataset, and of the hovered pair on top of it.
+#[component]
+pub fn DistributionPanel(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    hovered: ReadOnlySignal<Vec<usize>>,
+) -> Element {
+    let mut distribution = use_signal(Distribution::default);
+    let dataset_times = use_memo(move || ContactTimes::of_dataset(current_dataset.read().links()));
+    let pair_times = use_memo(move || match hovered.read().as_slice() {
+        [u, v] if u != v => Some(ContactTimes::of_pair(
+            current_dataset.read().links(),
+            *u,
+            *v,
+        )),
+        _ => None,
+    });
+
+    let format = current_dataset.read().time_format();
+    let values = distribution().values(&dataset_times.read());
+    let bins = log_histogram(&values, 5);
+    let pair_bins = pair_times
+        .read()
+        .as_ref()
+        .map(|t| log_histogram(&distribution().values(t), 5))
+        .unwrap_or_default();
+    let zeros = values.iter().filter(|x| **x == 0).count();
+
+    // log10 bounds of both axes, from the dataset histogram
+    let x_min = bins.first().map_or(0., |b| b.start.log10());
+    let x_max = bins.last().map_or(1., |b| b.end.log10());
+    let d_min = bins
+        .iter()
+        .map(|b| b.density.log10())
+        .fold(f64::INFINITY, f64::min);
+    let d_max = bins
+        .iter()
+        .map(|b| b.density.log10())
+        .fold(f64::NEG_INFINITY, f64::max);
+    let point = move |b: &Bin| {
+        let x = ((b.start * b.end).sqrt().log10() - x_min) / (x_max - x_min).max(1e-9) * 100.;
+        let y = 60. - (b.density.log10() - d_min) / (d_max - d_min).max(1e-9) * 55. - 2.5;
+        (x, y)
+    };
+    let line = |bins: &[Bin]| {
+        bins.iter()
+            .map(|b| {
+                let (x, y) = point(b);
+                format!("{x},{y}")
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let coefficient = |x: Option<f64>| x.map_or("-".to_string(), |x| format!("{x:.3}"));
+
+    rsx! {
+        div { class: "style-select",
+            select {
+                onchange: move |e: Event<FormData>| {
+                    if let Some(d) = Distribution::ALL.into_iter().find(|d| d.label() == e.value()) {
+                        distribution.set(d)
+                    }
+                },
+                for d in Distribution::ALL {
+                    option { value: d.label(), selected: d == distribution(), "{d.label()}" }
+                }
+            }
+        }
+        svg {
+            class: "log-histogram",
+            view_box: "0 0 100 60",
+            width: "100%",
+            polyline { class: "distribution-dataset", points: line(&bins) }
+            for b in bins.iter() {
+                circle {
+                    class: "distribution-dataset",
+                    cx: point(b).0,
+                    cy: point(b).1,
+                    r: 1,
+                    title { "{format.format_duration(b.start as u64)} - {format.format_duration(b.end as u64)}: {b.density:.2e}" }
+                }
+            }
+            polyline { class: "distribution-pair", points: line(&pair_bins) }
+        }
+        if let (Some(first), Some(last)) = (bins.first(), bins.last()) {
+            div { class: "histogram-range",
+                span { "{format.format_duration(first.start as u64)}" }
+                span { "{format.format_duration(last.end as u64)}" }
+            }
+        }
+        div { class: "data-output",
+            if zeros > 0 {
+                p { "{zeros} values of zero not shown" }
+            }
+            p { "burstiness: {coefficient(dataset_times.read().burstiness())}" }
+            p { "memory: {coefficient(dataset_times.read().memory())}" }
+        }
+        if let Some(times) = pair_times() {
+            div { class: "data-output",
+                h3 { "hovered pair" }
+                p { "contacts: {times.durations.len()}" }
+                p { "burstiness: {coefficient(times.burstiness())}" }
+                p { "memory: {coefficient(times.memory())}" }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(n1: usize, n2: usize, start: u64, end: u64) -> Link {
+        Link { n1, n2, start, end }
+    }
+
+    fn gaps(gaps: &[u64]) -> ContactTimes {
+        ContactTimes {
+            durations: Vec::new(),
+            gaps: vec![gaps.to_vec()],
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn contacts_merge_overlapping_links() {
+        let links = [
+            link(0, 1, 0, 5),
+            link(1, 0, 3, 8),
+            link(0, 1, 10, 12),
+            link(2, 2, 0, 9),
+        ];
+        let times = ContactTimes::of_dataset(&links);
+        assert_eq!(times.durations, vec![8, 2]);
+        assert_eq!(times.gaps, vec![vec![2]]);
+    }
+
+    #[test]
+    fn burstiness() {
+        // periodic contacts
+        let links: Vec<Link> = (0..4).map(|k| link(0, 1, 10 * k, 10 * k + 1)).collect();
+        let times = ContactTimes::of_dataset(&links);
+        assert_eq!(times.inter_contact_times(), vec![9, 9, 9]);
+        assert_eq!(times.burstiness(), Some(-1.));
+        // mean 2 and standard deviation 1
+        assert!(close(gaps(&[1, 3]).burstiness().unwrap(), -1. / 3.));
+        assert_eq!(gaps(&[4]).burstiness(), None);
+    }
+
+    #[test]
+    fn memory() {
+        // consecutive pairs (1, 2), (2, 3), (3, 4)
+        assert!(close(gaps(&[1, 2, 3, 4]).memory().unwrap(), 1.));
+        assert!(close(gaps(&[1, 3, 1, 3, 1]).memory().unwrap(), -1.));
+        // constant gaps have no correlation
+        assert_eq!(gaps(&[2, 2, 2]).memory(), None);
+    }
+
+    #[test]
+    fn log_histogram_bins() {
+        let bins = log_histogram(&[1, 5, 10, 0, 100], 1);
+        let edges: Vec<(f64, f64)> = bins.iter().map(|b| (b.start, b.end)).collect();
+        assert_eq!(edges, vec![(1., 10.), (10., 100.), (100., 1000.)]);
+        assert!(close(bins[0].density, 2. / 4. / 9.));
+        assert!(close(bins[2].density, 1. / 4. / 900.));
+        // two bins per decade, split at √10
+        let bins = log_histogram(&[3, 4], 2);
+        assert_eq!(bins.len(), 2);
+        assert!(close(bins[0].end, 10f64.sqrt()) && close(bins[1].start, 10f64.sqrt()));
+        assert!(log_histogram(&[0, 0], 2).is_empty());
+    }
+}
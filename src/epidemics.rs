@@ -0,0 +1,409 @@
+//! SI, SIR and SIS spreading processes on the contacts of a link stream.
+//!
+//! Infections are processed in time order, as in `temporal_paths`: when a
+//! node is infected, each contact it has while infectious may transmit to
+//! the other node, which is infected then if it is still susceptible.
+
+use crate::graph_data::Highlight;
+use crate::temporal_paths::Adjacency;
+use crate::utils::PlayButton;
+use crate::LinkStream;
+use dioxus::prelude::*;
+use fastrand::Rng;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::Range;
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum EpidemicModel {
+    /// infected nodes stay infected
+    #[default]
+    Si,
+    /// infected nodes recover and become immune
+    Sir,
+    /// infected nodes recover and can be infected again
+    Sis,
+}
+
+impl EpidemicModel {
+    pub const ALL: [EpidemicModel; 3] = [EpidemicModel::Si, EpidemicModel::Sir, EpidemicModel::Sis];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EpidemicModel::Si => "SI",
+            EpidemicModel::Sir => "SIR",
+            EpidemicModel::Sis => "SIS",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Transmission {
+    /// probability of transmission of each contact
+    PerContact(f64),
+    /// rate of transmission per time unit of contact
+    PerDuration(f64),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct EpidemicParams {
+    pub model: EpidemicModel,
+    pub transmission: Transmission,
+    /// time during which a node is infectious, for SIR and SIS
+    pub recovery: u64,
+    /// nodes infected at `start`
+    pub seeds: Vec<usize>,
+    pub start: u64,
+    pub rng_seed: u64,
+}
+
+impl Default for EpidemicParams {
+    fn default() -> Self {
+        Self {
+            model: EpidemicModel::default(),
+            transmission: Transmission::PerContact(0.5),
+            recovery: 3600,
+            seeds: Vec::new(),
+            start: 0,
+            rng_seed: 0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum State {
+    Susceptible,
+    Infected,
+    Recovered,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Epidemic {
+    pub model: EpidemicModel,
+    /// periods during which each node is infected, in time order
+    pub infections: Vec<Vec<Range<u64>>>,
+}
+
+impl Epidemic {
+    pub fn state(&self, node: usize, t: u64) -> State {
+        let periods = &self.infections[node];
+        if periods.iter().any(|p| p.contains(&t)) {
+            State::Infected
+        } else if self.model == EpidemicModel::Sir && periods.iter().any(|p| p.end <= t) {
+            State::Recovered
+        } else {
+            State::Susceptible
+        }
+    }
+
+    /// First infection time of each node.
+    pub fn infection_times(&self) -> Vec<Option<u64>> {
+        self.infections
+            .iter()
+            .map(|p| p.first().map(|p| p.start))
+            .collect()
+    }
+
+    /// Number of infected nodes, after each time it changes.
+    pub fn prevalence(&self) -> Vec<(u64, usize)> {
+        let mut changes: Vec<(u64, i64)> = self
+            .infections
+            .iter()
+            .flatten()
+            .flat_map(|p| [(p.start, 1), (p.end, -1)])
+            .collect();
+        changes.sort();
+        let mut result: Vec<(u64, usize)> = Vec::new();
+        let mut count = 0;
+        for (t, c) in changes {
+            count += c;
+            match result.last_mut() {
+                Some(last) if last.0 == t => last.1 = count as usize,
+                _ => result.push((t, count as usize)),
+            }
+        }
+        result
+    }
+}
+
+pub fn simulate(stream: &LinkStream, params: &EpidemicParams) -> Epidemic {
+    let end = stream.time_window().end;
+    let adjacency = Adjacency::new(stream, params.start..end);
+    let mut rng = Rng::with_seed(params.rng_seed);
+    let mut infections: Vec<Vec<Range<u64>>> = vec![Vec::new(); stream.node_count()];
+    let mut queue = BinaryHeap::new();
+    for s in &params.seeds {
+        queue.push(Reverse((params.start, *s)));
+    }
+
+    while let Some(Reverse((t, v))) = queue.pop() {
+        let susceptible = match (params.model, infections[v].last()) {
+            (_, None) => true,
+            (EpidemicModel::Sis, Some(last)) => last.end <= t,
+            _ => false,
+        };
+        if !susceptible {
+            continue;
+        }
+        let recovery = match params.model {
+            EpidemicModel::Si => end,
+            _ => (t + params.recovery).min(end),
+        };
+        infections[v].push(t..recovery);
+
+        for &(w, start, stop) in adjacency.neighbours(v) {
+            let (from, to) = (start.max(t), stop.min(recovery));
+            if from > to {
+                continue;
+            }
+            let infection = match params.transmission {
+                Transmission::PerContact(p) => (rng.f64() < p).then_some(from),
+                Transmission::PerDuration(rate) => {
+                    let delay = -(1. - rng.f64()).ln() / rate;
+                    (from as f64 + delay <= to as f64).then_some(from + delay as u64)
+                }
+            };
+            if let Some(time) = infection {
+                queue.push(Reverse((time, w)));
+            }
+        }
+    }
+
+    Epidemic {
+        model: params.model,
+        infections,
+    }
+}
+
+/// State of every node at time `t`.
+pub fn epidemic_highlight(params: &EpidemicParams, epidemic: &Epidemic, t: u64) -> Highlight {
+    let mut highlight = Highlight::default();
+    for i in 0..epidemic.infections.len() {
+        let class = match epidemic.state(i, t) {
+            State::Infected => "infected",
+            State::Recovered => "recovered",
+            State::Susceptible => "susceptible",
+        };
+        highlight.node_classes.push((i, class.to_string()));
+        if params.seeds.contains(&i) {
+            highlight
+                .node_classes
+                .push((i, "epidemic-seed".to_string()));
+        }
+    }
+    highlight
+}
+
+/// Parameters of the simulation, prevalence curve over the time window,
+/// and playback of the spreading.
+#[component]
+pub fn EpidemicPanel(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    time_window: ReadOnlySignal<Range<u64>>,
+    mut params: Signal<EpidemicParams>,
+    epidemic: ReadOnlySignal<Option<Epidemic>>,
+    time: ReadOnlySignal<u64>,
+    r_value: Signal<f64>,
+) -> Element {
+    let dataset = current_dataset.read();
+    let format = dataset.time_format();
+    let n = dataset.node_count();
+    let Range { start, end } = dataset.time_window();
+    let recoveries: Vec<u64> = format
+        .nice_durations()
+        .take_while(|d| *d < end - start)
+        .collect();
+    let current = params();
+    let (per_duration, beta) = match current.transmission {
+        Transmission::PerContact(p) => (false, p),
+        Transmission::PerDuration(r) => (true, r),
+    };
+
+    let Range { start, end } = time_window();
+    let x = |t: u64| (t.clamp(start, end) - start) as f64 / (end - start).max(1) as f64 * 100.;
+    let y = |count: usize| 20. - 20. * count as f64 / n.max(1) as f64;
+    let curve = epidemic().map(|e| {
+        let mut points = Vec::new();
+        let mut previous = 0;
+        for (t, count) in e.prevalence() {
+            points.push(format!("{},{}", x(t), y(previous)));
+            points.push(format!("{},{}", x(t), y(count)));
+            previous = count;
+        }
+        points.push(format!("{},{}", x(end), y(previous)));
+        points.join(" ")
+    });
+
+    rsx! {
+        div { class: "style-select",
+            span { "model" }
+            select {
+                onchange: move |e: Event<FormData>| {
+                    if let Some(m) = EpidemicModel::ALL.into_iter().find(|m| m.label() == e.value()) {
+                        params.write().model = m
+                    }
+                },
+                for m in EpidemicModel::ALL {
+                    option { value: m.label(), selected: m == current.model, "{m.label()}" }
+                }
+            }
+        }
+        div { class: "style-select",
+            span { "transmission" }
+            select {
+                onchange: move |e: Event<FormData>| {
+                    let beta = match params().transmission {
+                        Transmission::PerContact(p) | Transmission::PerDuration(p) => p,
+                    };
+                    params.write().transmission = if e.value() == "duration" {
+                        Transmission::PerDuration(beta)
+                    } else {
+                        Transmission::PerContact(beta.min(1.))
+                    }
+                },
+                option { value: "contact", selected: !per_duration, "per contact" }
+                option { value: "duration", selected: per_duration, "per time unit" }
+            }
+            input {
+                r#type: "number",
+                min: "0",
+                step: "any",
+                value: "{beta}",
+                onchange: move |e| {
+                    let beta: f64 = e.value().parse().unwrap_or(0.);
+                    let mut p = params.write();
+                    p.transmission = match p.transmission {
+                        Transmission::PerContact(_) => Transmission::PerContact(beta.clamp(0., 1.)),
+                        Transmission::PerDuration(_) => Transmission::PerDuration(beta.max(0.)),
+                    }
+                }
+            }
+        }
+        if current.model != EpidemicModel::Si {
+            div { class: "style-select",
+                span { "recovery" }
+                select {
+                    onchange: move |e: Event<FormData>| {
+                        if let Ok(r) = e.value().parse() {
+                            params.write().recovery = r
+                        }
+                    },
+                    for r in recoveries {
+                        option { value: "{r}", selected: r == current.recovery, "{format.format_duration(r)}" }
+                    }
+                }
+            }
+        }
+        div { class: "style-select",
+            span { "random seed" }
+            input {
+                r#type: "number",
+                min: "0",
+                value: "{current.rng_seed}",
+                onchange: move |e| params.write().rng_seed = e.value().parse().unwrap_or(0)
+            }
+        }
+        if current.seeds.is_empty() {
+            div { class: "default-output", "click on nodes to choose the first infected ones" }
+        } else {
+            div { class: "data-output",
+                p { "{current.seeds.len()} seed nodes infected at {format.format_time(current.start)}" }
+                if let Some(e) = epidemic() {
+                    p {
+                        "infected now: {(0..n).filter(|i| e.state(*i, time()) == State::Infected).count()} / {n}"
+                    }
+                    p { "ever infected: {e.infection_times().iter().flatten().count()} / {n}" }
+                }
+                button { onclick: move |_| params.write().start = time(), "start at current time" }
+                button { onclick: move |_| params.write().seeds.clear(), "clear" }
+                PlayButton { r_value }
+            }
+        }
+        if let Some(points) = curve {
+            svg {
+                class: "prevalence",
+                preserve_aspect_ratio: "none",
+                view_box: "0 0 100 20",
+                width: "100%",
+                polyline { class: "prevalence-curve", points: points }
+                line {
+                    class: "prevalence-cursor",
+                    x1: x(time()),
+                    x2: x(time()),
+                    y1: 0,
+                    y2: 20
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linkstream::test_stream;
+
+    // a-b during [0, 1], b-c during [3, 4], c-a during [8, 9]
+    fn triangle() -> LinkStream {
+        test_stream(
+            &["a", "b", "c"],
+            &[(0, 1, 0, 1), (1, 2, 3, 4), (2, 0, 8, 9)],
+            0..100,
+        )
+    }
+
+    fn params(model: EpidemicModel) -> EpidemicParams {
+        EpidemicParams {
+            model,
+            transmission: Transmission::PerContact(1.),
+            recovery: 5,
+            seeds: vec![0],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn si_infects_every_reachable_node() {
+        let epidemic = simulate(&triangle(), &params(EpidemicModel::Si));
+        assert_eq!(
+            epidemic.infections,
+            vec![vec![0..100], vec![0..100], vec![3..100]]
+        );
+    }
+
+    #[test]
+    fn sir_nodes_recover_for_good() {
+        let epidemic = simulate(&triangle(), &params(EpidemicModel::Sir));
+        assert_eq!(
+            epidemic.infections,
+            vec![vec![0..5], vec![0..5], vec![3..8]]
+        );
+        assert_eq!(epidemic.state(0, 6), State::Recovered);
+        assert_eq!(epidemic.state(2, 6), State::Infected);
+    }
+
+    #[test]
+    fn sis_nodes_are_infected_again() {
+        let epidemic = simulate(&triangle(), &params(EpidemicModel::Sis));
+        // c infects a again at 8, when it recovers, and a infects it back
+        assert_eq!(
+            epidemic.infections,
+            vec![vec![0..5, 8..13], vec![0..5], vec![3..8, 8..13]]
+        );
+        assert_eq!(epidemic.state(0, 6), State::Susceptible);
+        assert_eq!(epidemic.prevalence().last(), Some(&(13, 0)));
+    }
+
+    #[test]
+    fn simulations_depend_on_the_seed_only() {
+        let mut p = params(EpidemicModel::Sir);
+        p.transmission = Transmission::PerContact(0.5);
+        p.rng_seed = 7;
+        assert_eq!(simulate(&triangle(), &p), simulate(&triangle(), &p));
+        p.transmission = Transmission::PerContact(0.);
+        assert_eq!(
+            simulate(&triangle(), &p).infections,
+            vec![vec![0..5], vec![], vec![]]
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Hiding nodes and edges of the aggregated graph.
+//!
+//! Node sets and attributes do not depend on time, so they also filter the
+//! links counted in the timelines. The other criteria depend on the window.
+
+use crate::node_style::categories;
+use crate::LinkStream;
+use dioxus::prelude::*;
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum SetMode {
+    /// hide the nodes of the set
+    #[default]
+    Exclude,
+    /// hide the nodes outside of the set, unless it is empty
+    Include,
+}
+
+impl SetMode {
+    pub const ALL: [SetMode; 2] = [SetMode::Exclude, SetMode::Include];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SetMode::Exclude => "exclude",
+            SetMode::Include => "only",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Filter {
+    /// hide nodes without any link in the aggregation window
+    pub hide_inactive: bool,
+    /// fraction of the heaviest edge of the window below which edges are hidden
+    pub min_weight: f64,
+    /// number of strongest edges kept for each node
+    pub top_k: Option<usize>,
+    pub set_mode: SetMode,
+    pub node_set: Vec<usize>,
+    /// name and value of the attribute the nodes must have
+    pub attribute: Option<(String, String)>,
+}
+
+impl Filter {
+    /// Nodes kept by the node set and the attribute.
+    pub fn node_mask(&self, stream: &LinkStream) -> Vec<bool> {
+        let values = self
+            .attribute
+            .as_ref()
+            .and_then(|(name, value)| Some((stream.node_attribute(name)?, value)));
+        (0..stream.node_count())
+            .map(|i| {
+                let in_set = self.node_set.contains(&i);
+                let set = match self.set_mode {
+                    SetMode::Exclude => !in_set,
+                    SetMode::Include => self.node_set.is_empty() || in_set,
+                };
+                let attribute = values.is_none_or(|(values, value)| {
+                    values[i].as_ref().is_some_and(|v| v.to_string() == *value)
+                });
+                set && attribute
+            })
+            .collect()
+    }
+
+    /// Edges between visible nodes, at least as heavy as `min_weight` and
+    /// among the `top_k` strongest edges of one of their ends.
+    pub fn kept_edges(&self, weights: &[Vec<f64>], visible: &[bool]) -> Vec<Vec<bool>> {
+        let n = weights.len();
+        let kept: Vec<Vec<bool>> = (0..n)
+            .map(|a| {
+                (0..n)
+                    .map(|b| {
+                        let w = weights[a][b];
+                        a != b && visible[a] && visible[b] && w > 0. && w >= self.min_weight
+                    })
+                    .collect()
+            })
+            .collect();
+        let Some(k) = self.top_k else {
+            return kept;
+        };
+        let mut strongest = vec![vec![false; n]; n];
+        for (a, row) in kept.iter().enumerate() {
+            let mut others: Vec<usize> = (0..n).filter(|b| row[*b]).collect();
+            others.sort_by(|x, y| weights[a][*y].total_cmp(&weights[a][*x]));
+            for b in others.into_iter().take(k) {
+                strongest[a][b] = true;
+                strongest[b][a] = true;
+            }
+        }
+        strongest
+    }
+}
+
+#[component]
+pub fn FilterPanel(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    mut filter: Signal<Filter>,
+) -> Element {
+    let dataset = current_dataset.read();
+    let names: Vec<String> = dataset.node_names().map(|x| x.to_string()).collect();
+    let attributes: Vec<String> = dataset.attribute_names().map(|x| x.to_string()).collect();
+    let current = filter();
+    let attribute = current.attribute.clone().map(|(name, _)| name);
+    let values = attribute
+        .as_ref()
+        .and_then(|name| dataset.node_attribute(name))
+        .map(categories)
+        .unwrap_or_default();
+
+    rsx! {
+        div { class: "style-select",
+            span { "hide inactive nodes" }
+            input {
+                r#type: "checkbox",
+                checked: current.hide_inactive,
+                onchange: move |e| filter.write().hide_inactive = e.checked()
+            }
+        }
+        div { class: "style-select",
+            span { "min edge weight" }
+            input {
+                r#type: "range",
+                min: "0",
+                max: "1",
+                step: "0.05",
+                value: "{current.min_weight}",
+                oninput: move |e| filter.write().min_weight = e.value().parse().unwrap_or(0.)
+            }
+            span { "{current.min_weight:.2}" }
+        }
+        div { class: "style-select",
+            span { "strongest edges per node" }
+            input {
+                r#type: "number",
+                min: "0",
+                placeholder: "all",
+                value: current.top_k.map(|k| k.to_string()).unwrap_or_default(),
+                onchange: move |e| {
+                    filter.write().top_k = e.value().parse().ok().filter(|k| *k > 0)
+                }
+            }
+        }
+        div { class: "style-select",
+            span { "nodes" }
+            select {
+                onchange: move |e: Event<FormData>| {
+                    if let Some(m) = SetMode::ALL.into_iter().find(|m| m.label() == e.value()) {
+                        filter.write().set_mode = m
+                    }
+                },
+                for m in SetMode::ALL {
+                    option { value: m.label(), selected: m == current.set_mode, "{m.label()}" }
+                }
+            }
+            input {
+                class: "node-search",
+                list: "filter-node-names",
+                placeholder: "add a node",
+                onchange: move |e| {
+                    let i = current_dataset.read().node_names().position(|name| name == e.value());
+                    if let Some(i) = i {
+                        let mut f = filter.write();
+                        if !f.node_set.contains(&i) {
+                            f.node_set.push(i);
+                        }
+                    }
+                }
+            }
+            datalist { id: "filter-node-names",
+                for name in names.iter() {
+                    option { value: "{name}" }
+                }
+            }
+        }
+        if !current.node_set.is_empty() {
+            div { class: "filter-nodes",
+                for i in current.node_set.iter().copied() {
+                    button {
+                        class: "filter-node",
+                        onclick: move |_| filter.write().node_set.retain(|j| *j != i),
+                        "{names[i]} ×"
+                    }
+                }
+            }
+        }
+        if !attributes.is_empty() {
+            div { class: "style-select",
+                span { "attribute" }
+                select {
+                    onchange: move |e: Event<FormData>| {
+                        let name = e.value();
+                        filter.write().attribute = if name.is_empty() {
+                            None
+                        } else {
+                            // first value of the attribute
+                            let dataset = current_dataset.read();
+                            let value = dataset.node_attribute(&name).map(categories).unwrap_or_default();
+                            value.first().map(|v| (name, v.clone()))
+                        }
+                    },
+                    option { value: "", selected: attribute.is_none(), "none" }
+                    for name in attributes {
+                        option {
+                            value: "{name}",
+                            selected: attribute.as_ref() == Some(&name),
+                            "{name}"
+                        }
+                    }
+                }
+                if let Some((name, value)) = current.attribute.clone() {
+                    select {
+                        onchange: move |e: Event<FormData>| {
+                            filter.write().attribute = Some((name.clone(), e.value()))
+                        },
+                        for v in values {
+                            option { value: "{v}", selected: v == value, "{v}" }
+                        }
+                    }
+                }
+            }
+        }
+        button { onclick: move |_| filter.set(Filter::default()), "reset" }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linkstream::{Attribute, LinkStreamData};
+    use crate::time_format::TimeUnit;
+
+    fn stream() -> LinkStream {
+        let names = ["a", "b", "c", "d"].map(String::from).to_vec();
+        let class = [Some("x"), Some("y"), None, Some("x")]
+            .map(|c| c.map(|c| Attribute::Categorical(c.to_string())));
+        let age = [1., 2., 1., 3.].map(|a| Some(Attribute::Numeric(a)));
+        let data = LinkStreamData::new(names, Vec::new(), 0..10, TimeUnit::Steps)
+            .with_node_attribute("class", class.to_vec())
+            .with_node_attribute("age", age.to_vec());
+        LinkStream::new("small".to_string(), data)
+    }
+
+    #[test]
+    fn node_sets() {
+        let mut filter = Filter {
+            node_set: vec![1, 2],
+            ..Default::default()
+        };
+        assert_eq!(filter.node_mask(&stream()), vec![true, false, false, true]);
+        filter.set_mode = SetMode::Include;
+        assert_eq!(filter.node_mask(&stream()), vec![false, true, true, false]);
+        // an empty set keeps everything
+        filter.node_set.clear();
+        assert_eq!(filter.node_mask(&stream()), vec![true; 4]);
+    }
+
+    #[test]
+    fn attributes() {
+        let mut filter = Filter {
+            attribute: Some(("class".to_string(), "x".to_string())),
+            ..Default::default()
+        };
+        // unknown values do not match
+        assert_eq!(filter.node_mask(&stream()), vec![true, false, false, true]);
+        filter.attribute = Some(("age".to_string(), "1".to_string()));
+        assert_eq!(filter.node_mask(&stream()), vec![true, false, true, false]);
+        // with the node set
+        filter.node_set = vec![0];
+        assert_eq!(filter.node_mask(&stream()), vec![false, false, true, false]);
+    }
+
+    #[test]
+    fn strongest_edges_are_kept_from_both_ends() {
+        // star around 0, with a heavier edge 1-2
+        let weights = vec![
+            vec![0., 0.5, 0.4, 0.3],
+            vec![0.5, 0., 1., 0.],
+            vec![0.4, 1., 0., 0.],
+            vec![0.3, 0., 0., 0.],
+        ];
+        let filter = Filter {
+            top_k: Some(1),
+            ..Default::default()
+        };
+        let kept = filter.kept_edges(&weights, &[true; 4]);
+        // 0-3 is the only edge of 3, 1-2 the strongest of 1 and 2, 0-1 the strongest of 0
+        let edges: Vec<(usize, usize)> = (0..4)
+            .flat_map(|a| (0..4).map(move |b| (a, b)))
+            .filter(|(a, b)| kept[*a][*b])
+            .collect();
+        assert_eq!(edges, vec![(0, 1), (0, 3), (1, 0), (1, 2), (2, 1), (3, 0)]);
+
+        // the weight and the hidden nodes come first
+        let filter = Filter {
+            top_k: Some(1),
+            min_weight: 0.35,
+            ..Default::default()
+        };
+        let kept = filter.kept_edges(&weights, &[true, true, false, true]);
+        assert!(kept[0][1] && kept[1][0]);
+        assert!(!kept[1][2] && !kept[0][3]);
+    }
+}
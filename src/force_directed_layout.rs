@@ -16,7 +16,7 @@ pub fn compute(
     params: ForceDirectedLayoutParams,
 ) -> Vec<Vec2> {
     let mut positions: Vec<Vec2> = (0..n)
-        .map(|x| x as f64 / n as f64 * 6.2831)
+        .map(|x| x as f64 / n as f64 * std::f64::consts::TAU)
         .map(Vec2::from_angle)
         .map(|v| v + Vec2::new(1., 1.))
         .collect();
@@ -0,0 +1,266 @@
+//! Synthetic link streams, with time counted in steps.
+
+use crate::linkstream::{Attribute, Link, LinkStreamData};
+use crate::time_format::TimeUnit;
+use fastrand::Rng;
+use std::ops::RangeInclusive;
+
+/// numbers of nodes and steps accepted, so that generating stays fast
+pub const NODES: RangeInclusive<usize> = 2..=200;
+pub const DURATION: RangeInclusive<u64> = 100..=10_000;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Generator {
+    /// each pair is linked during a step with the same probability
+    ErdosRenyi,
+    /// active nodes link to random nodes, activities follow a power law
+    /// (Perra et al., 2012)
+    ActivityDriven,
+    /// Poisson contacts, more frequent inside communities
+    Communities,
+    /// contacts of some pairs at regular intervals
+    Periodic,
+    /// contacts of some pairs with heavy tailed inter-contact times
+    Bursty,
+}
+
+/// Model and size of a synthetic link stream, clamped to `NODES` and `DURATION`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GeneratorParams {
+    pub model: Generator,
+    pub nodes: usize,
+    /// number of steps
+    pub duration: u64,
+    pub seed: u64,
+}
+
+fn exponential(rng: &mut Rng, mean: f64) -> f64 {
+    -(1. - rng.f64()).ln() * mean
+}
+
+fn pairs(n: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..n).flat_map(move |u| (u + 1..n).map(move |v| (u, v)))
+}
+
+impl Generator {
+    pub const ALL: [Generator; 5] = [
+        Generator::ErdosRenyi,
+        Generator::ActivityDriven,
+        Generator::Communities,
+        Generator::Periodic,
+        Generator::Bursty,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Generator::ErdosRenyi => "temporal Erdős–Rényi",
+            Generator::ActivityDriven => "activity driven",
+            Generator::Communities => "Poisson with communities",
+            Generator::Periodic => "periodic",
+            Generator::Bursty => "bursty",
+        }
+    }
+}
+
+impl GeneratorParams {
+    pub fn generate(&self) -> LinkStreamData {
+        let mut rng = Rng::with_seed(self.seed);
+        let n = self.nodes.clamp(*NODES.start(), *NODES.end());
+        let t_max = self.duration.clamp(*DURATION.start(), *DURATION.end());
+        let link = |n1, n2, start: f64, end: f64| Link {
+            n1,
+            n2,
+            start: (start as u64).min(t_max),
+            end: (end.ceil() as u64).min(t_max),
+        };
+        let mut links = Vec::new();
+        let mut communities = None;
+
+        match self.model {
+            Generator::ErdosRenyi => {
+                // a quarter of the nodes are linked at each step, on average
+                let p = 0.5 / (n - 1) as f64;
+                // steps between two links of a pair follow a geometric law
+                let skip = |rng: &mut Rng| ((1. - rng.f64()).ln() / (1. - p).ln()) as u64;
+                for (u, v) in pairs(n) {
+                    let mut t = skip(&mut rng);
+                    while t < t_max {
+                        links.push(link(u, v, t as f64, (t + 1) as f64));
+                        t += 1 + skip(&mut rng);
+                    }
+                }
+            }
+            Generator::ActivityDriven => {
+                // activities between EPSILON and 1, with density a^-GAMMA
+                const GAMMA: f64 = 2.1;
+                const EPSILON: f64 = 0.01;
+                const M: usize = 2;
+                let e = EPSILON.powf(1. - GAMMA);
+                let activities: Vec<f64> = (0..n)
+                    .map(|_| (e + rng.f64() * (1. - e)).powf(1. / (1. - GAMMA)))
+                    .collect();
+                for t in 0..t_max {
+                    for (u, a) in activities.iter().enumerate() {
+                        if rng.f64() >= *a {
+                            continue;
+                        }
+                        for _ in 0..M {
+                            let v = (u + 1 + rng.usize(..n - 1)) % n;
+                            links.push(link(u, v, t as f64, (t + 1) as f64));
+                        }
+                    }
+                }
+            }
+            Generator::Communities => {
+                let k = n.clamp(1, 4);
+                let community: Vec<usize> = (0..n).map(|u| u * k / n).collect();
+                // expected number of contacts of a pair during the whole stream
+                let (inside, outside) = (20., 2.);
+                let mean_duration = (t_max as f64 / 500.).max(1.);
+                for (u, v) in pairs(n) {
+                    let count = if community[u] == community[v] {
+                        inside
+                    } else {
+                        outside
+                    };
+                    let mut t = exponential(&mut rng, t_max as f64 / count);
+                    while t < t_max as f64 {
+                        let d = exponential(&mut rng, mean_duration);
+                        links.push(link(u, v, t, t + d));
+                        t += d + exponential(&mut rng, t_max as f64 / count);
+                    }
+                }
+                communities = Some(community);
+            }
+            Generator::Periodic => {
+                let period = (t_max as f64 / 10.).max(1.);
+                for (u, v) in pairs(n) {
+                    if rng.f64() > 0.3 {
+                        continue;
+                    }
+                    let phase = rng.f64() * period;
+                    let mut t = phase;
+                    while t < t_max as f64 {
+                        let jitter = (rng.f64() - 0.5) * period / 10.;
+                        let start = (t + jitter).max(0.);
+                        links.push(link(u, v, start, start + period / 20.));
+                        t += period;
+                    }
+                }
+            }
+            Generator::Bursty => {
+                // Pareto inter-contact times, with infinite variance
+                const ALPHA: f64 = 1.5;
+                let x_min = (t_max as f64 / 200.).max(1.);
+                for (u, v) in pairs(n) {
+                    if rng.f64() > 0.3 {
+                        continue;
+                    }
+                    let mut t = rng.f64() * t_max as f64;
+                    while t < t_max as f64 {
+                        links.push(link(u, v, t, t + 1.));
+                        t += x_min * (1. - rng.f64()).powf(-1. / ALPHA);
+                    }
+                }
+            }
+        }
+
+        // some models can draw no link at all for small streams
+        if links.is_empty() {
+            links.push(link(0, 1, 0., 1.));
+        }
+        links.sort_by_key(|l| l.start);
+        let names = (0..n).map(|u| format!("n{u}")).collect();
+        let data = LinkStreamData::new(names, links, 0..t_max, TimeUnit::Steps);
+        match communities {
+            Some(c) => data.with_node_attribute(
+                "community",
+                c.into_iter()
+                    .map(|c| Some(Attribute::Categorical(format!("c{c}"))))
+                    .collect(),
+            ),
+            None => data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(model: Generator, nodes: usize, seed: u64) -> LinkStreamData {
+        GeneratorParams {
+            model,
+            nodes,
+            duration: 1000,
+            seed,
+        }
+        .generate()
+    }
+
+    fn assert_near(count: usize, expected: f64, tolerance: f64) {
+        let error = (count as f64 - expected).abs() / expected;
+        assert!(error < tolerance, "{count} links, {expected} expected");
+    }
+
+    #[test]
+    fn generators_depend_on_the_seed_only() {
+        for model in Generator::ALL {
+            let data = generate(model, 20, 1);
+            assert_eq!(data, generate(model, 20, 1), "{model:?}");
+            assert_ne!(data, generate(model, 20, 2), "{model:?}");
+            assert!(data
+                .links()
+                .iter()
+                .all(|l| l.n1 != l.n2 && l.start <= l.end && l.end <= 1000));
+        }
+    }
+
+    #[test]
+    fn sizes_are_clamped() {
+        let params = GeneratorParams {
+            model: Generator::ErdosRenyi,
+            nodes: 0,
+            duration: 1,
+            seed: 0,
+        };
+        let data = params.generate();
+        assert!(data.links().iter().all(|l| l.n1 < 2 && l.n2 < 2));
+        assert_eq!(data.time_window(), 0..100);
+        // without any link drawn, one is added
+        let data = GeneratorParams {
+            model: Generator::Periodic,
+            ..params
+        }
+        .generate();
+        assert!(!data.links().is_empty());
+    }
+
+    #[test]
+    fn link_counts_in_expectation() {
+        // n t / 4 links
+        assert_near(
+            generate(Generator::ErdosRenyi, 20, 0).links().len(),
+            5000.,
+            0.05,
+        );
+        // M n t E[a], E[a] = 0.041 for the power law of the activities
+        assert_near(
+            generate(Generator::ActivityDriven, 200, 0).links().len(),
+            16400.,
+            0.3,
+        );
+        // 20 contacts for the 40 pairs inside the 4 communities, 2 for the 150 others
+        assert_near(
+            generate(Generator::Communities, 20, 0).links().len(),
+            1100.,
+            0.15,
+        );
+        // 10 contacts for 30% of the pairs
+        assert_near(
+            generate(Generator::Periodic, 20, 0).links().len(),
+            570.,
+            0.25,
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use crate::filters::Filter;
+use crate::node_style::{self, NodeStyle};
+use crate::render_graph::NodeShape;
+use crate::utils::Matrix;
+use crate::LinkStream;
+use std::ops::Range;
+
+/// Classes and edges drawn on top of the aggregated graph, to show the
+/// result of a computation (journeys, components...).
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Highlight {
+    pub node_classes: Vec<(usize, String)>,
+    pub edges: Vec<(usize, usize)>,
+}
+
+/// Aggregated graph of a time slice, with the appearance of its nodes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GraphData {
+    pub names: Vec<Option<String>>,
+    /// between 0 and 1
+    pub node_weights: Vec<f64>,
+    pub node_classes: Vec<Vec<String>>,
+    pub node_shapes: Option<Vec<NodeShape>>,
+    /// nodes not filtered out
+    pub visible: Vec<bool>,
+    pub edges: Vec<(usize, usize)>,
+    /// between 0 and 1
+    pub edge_weights: Vec<Vec<f64>>,
+}
+
+impl GraphData {
+    /// Edges come from `aggregation_window`, node sizes from `time_window`
+    /// unless the style maps them to an attribute.
+    pub fn new(
+        dataset: &LinkStream,
+        time_window: Range<u64>,
+        aggregation_window: Range<u64>,
+        style: &NodeStyle,
+    ) -> Self {
+        let n = dataset.node_count();
+
+        let (edge_weights, edges) = {
+            let mut edges = Vec::new();
+            let matrix = dataset.interaction_matrix(aggregation_window);
+            // empty windows have no heaviest edge
+            let m = matrix.matrix_max().max(f64::MIN_POSITIVE);
+
+            for (n1, row) in matrix.iter().enumerate() {
+                for (n2, &w) in row.iter().enumerate() {
+                    if w > 0. {
+                        edges.push((n1, n2));
+                    }
+                }
+            }
+
+            (matrix.matrix_map(|x| x / m), edges)
+        };
+
+        let attribute =
+            |name: &Option<String>| name.as_ref().and_then(|x| dataset.node_attribute(x));
+
+        let node_weights = match attribute(&style.size_by) {
+            Some(values) => node_style::node_sizes(values),
+            None => {
+                let matrix = dataset.interaction_matrix(time_window);
+                let node_weights = matrix.sum_one_level();
+                let m = node_weights.matrix_max().max(f64::MIN_POSITIVE);
+                node_weights.matrix_map(|x| x / m)
+            }
+        };
+
+        let node_classes = match attribute(&style.color_by) {
+            Some(values) => node_style::node_color_classes(values),
+            None => vec![vec![]; n],
+        };
+
+        Self {
+            names: dataset.node_names().map(|x| Some(x.to_string())).collect(),
+            node_weights,
+            node_classes,
+            node_shapes: attribute(&style.shape_by).map(node_style::node_shapes),
+            visible: vec![true; n],
+            edges,
+            edge_weights,
+        }
+    }
+
+    /// Size nodes by a measure instead of by attribute.
+    pub fn size_by_metric(&mut self, values: &[f64]) {
+        self.node_weights = node_style::metric_sizes(values);
+    }
+
+    /// Color nodes on a scale of the measure instead of by attribute.
+    pub fn color_by_metric(&mut self, values: &[f64]) {
+        self.node_classes = node_style::metric_color_classes(values);
+    }
+
+    /// Hide the nodes and edges rejected by `filter`.
+    pub fn apply_filter(&mut self, dataset: &LinkStream, filter: &Filter) {
+        self.visible = filter.node_mask(dataset);
+        let kept = filter.kept_edges(&self.edge_weights, &self.visible);
+        // after the edge filters, so that nodes left without edges are hidden too
+        if filter.hide_inactive {
+            for (v, row) in self.visible.iter_mut().zip(&kept) {
+                *v &= row.iter().any(|k| *k);
+            }
+        }
+        self.edges.retain(|(a, b)| kept[*a][*b]);
+        for (a, row) in self.edge_weights.iter_mut().enumerate() {
+            for (b, w) in row.iter_mut().enumerate() {
+                if !kept[a][b] {
+                    *w = 0.;
+                }
+            }
+        }
+        for (name, visible) in self.names.iter_mut().zip(&self.visible) {
+            if !visible {
+                *name = None;
+            }
+        }
+    }
+
+    /// Dim everything but `node`, its neighbours and the edges between them.
+    pub fn focus_ego(&mut self, node: usize) {
+        let n = self.node_classes.len();
+        let ego: Vec<bool> = (0..n)
+            .map(|i| i == node || self.edge_weights[node][i] > 0.)
+            .collect();
+        for (i, classes) in self.node_classes.iter_mut().enumerate() {
+            if !ego[i] {
+                classes.push("dimmed".to_string());
+            }
+        }
+        for (a, b) in &self.edges {
+            if !(ego[*a] && ego[*b]) {
+                self.edge_weights[*a][*b] *= 0.1;
+            }
+        }
+    }
+
+    /// Color nodes by community instead of by attribute.
+    pub fn color_communities(&mut self, labels: &[usize]) {
+        self.node_classes = node_style::community_color_classes(labels);
+    }
+}
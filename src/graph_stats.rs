@@ -0,0 +1,192 @@
+use crate::components::connected_components;
+use crate::metrics::StreamMetrics;
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::ops::Range;
+
+/// Statistics of the graph aggregated over a time window.
+/// Interaction times are clipped to the window, and self loops are ignored:
+/// a node is active if it is linked to another node during the window.
+/// Components are counted as in `components::component_series`.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct GraphStats {
+    pub active_nodes: usize,
+    pub active_links: usize,
+    pub density: f64,
+    pub average_degree: f64,
+    /// components of the subgraph induced by the active nodes
+    pub components: usize,
+    pub largest_component: usize,
+    pub interaction_time: u64,
+    /// (node, interaction time), most active first
+    pub top_nodes: Vec<(usize, u64)>,
+    /// ((n1, n2), interaction time), most active first
+    pub top_pairs: Vec<((usize, usize), u64)>,
+}
+
+impl GraphStats {
+    pub fn new(stream: &LinkStream, time_window: Range<u64>, top: usize) -> Self {
+        let n = stream.node_count();
+        let mut matrix = vec![vec![0.; n]; n];
+        let mut node_time = vec![0; n];
+        let mut interaction_time = 0;
+        for link in stream
+            .links_during(time_window.clone())
+            .filter(|l| l.n1 != l.n2)
+        {
+            let d = link.end.min(time_window.end) - link.start.max(time_window.start);
+            interaction_time += d;
+            node_time[link.n1] += d;
+            node_time[link.n2] += d;
+            matrix[link.n1][link.n2] += d as f64;
+            matrix[link.n2][link.n1] += d as f64;
+        }
+
+        let mut pairs = Vec::new();
+        for (i, row) in matrix.iter().enumerate() {
+            for (j, w) in row.iter().enumerate().skip(i + 1) {
+                if *w > 0. {
+                    pairs.push(((i, j), *w as u64));
+                }
+            }
+        }
+
+        let active: Vec<usize> = (0..n)
+            .filter(|i| matrix[*i].iter().any(|w| *w > 0.))
+            .collect();
+        let labels = connected_components(&matrix);
+        let mut component_sizes = vec![0; n];
+        for i in &active {
+            component_sizes[labels[*i]] += 1;
+        }
+
+        let mut top_nodes: Vec<(usize, u64)> = (0..n)
+            .map(|i| (i, node_time[i]))
+            .filter(|x| x.1 > 0)
+            .collect();
+        top_nodes.sort_by_key(|x| std::cmp::Reverse(x.1));
+        top_nodes.truncate(top);
+        let active_links = pairs.len();
+        pairs.sort_by_key(|x| std::cmp::Reverse(x.1));
+        pairs.truncate(top);
+
+        Self {
+            active_nodes: active.len(),
+            active_links,
+            density: if n > 1 {
+                active_links as f64 / (n * (n - 1) / 2) as f64
+            } else {
+                0.
+            },
+            average_degree: if n > 0 {
+                2. * active_links as f64 / n as f64
+            } else {
+                0.
+            },
+            components: component_sizes.iter().filter(|s| **s > 0).count(),
+            largest_component: component_sizes.iter().copied().max().unwrap_or(0),
+            interaction_time,
+            top_nodes,
+            top_pairs: pairs,
+        }
+    }
+}
+
+#[component]
+fn StatsTable(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    title: String,
+    stats: GraphStats,
+) -> Element {
+    let dataset = current_dataset.read();
+    let format = dataset.time_format();
+    let names: Vec<&str> = dataset.node_names().collect();
+    let name = |i: usize| names.get(i).copied().unwrap_or_default().to_string();
+
+    rsx! {
+        div { class: "data-output",
+            h3 { "{title}" }
+            p { "active nodes: {stats.active_nodes}" }
+            p { "active links: {stats.active_links}" }
+            p { "density: {stats.density:.3}" }
+            p { "average degree: {stats.average_degree:.2}" }
+            p { "components: {stats.components} (largest: {stats.largest_component})" }
+            p { "interaction time: {format.format_duration(stats.interaction_time)}" }
+            if !stats.top_nodes.is_empty() {
+                p { "most active nodes:" }
+                for (i , d) in stats.top_nodes {
+                    p { class: "hop", "{name(i)}: {format.format_duration(d)}" }
+                }
+            }
+            if !stats.top_pairs.is_empty() {
+                p { "most active pairs:" }
+                for ((a , b) , d) in stats.top_pairs {
+                    p { class: "hop", "{name(a)} - {name(b)}: {format.format_duration(d)}" }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn StatsPanel(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    time_window: ReadOnlySignal<Range<u64>>,
+    aggregation_window: ReadOnlySignal<Range<u64>>,
+) -> Element {
+    let window_stats = use_memo(move || GraphStats::new(&current_dataset.read(), time_window(), 5));
+    let aggregation_stats =
+        use_memo(move || GraphStats::new(&current_dataset.read(), aggregation_window(), 5));
+
+    rsx! {
+        StatsTable { current_dataset, title: "aggregation window", stats: aggregation_stats() }
+        StatsTable { current_dataset, title: "time window", stats: window_stats() }
+    }
+}
+
+/// Link stream measures of the time window, and of the hovered node or pair.
+#[component]
+pub fn StreamMetricsPanel(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    time_window: ReadOnlySignal<Range<u64>>,
+    hovered: ReadOnlySignal<Vec<usize>>,
+) -> Element {
+    let metrics = use_memo(move || StreamMetrics::new(&current_dataset.read(), time_window()));
+    let metrics = metrics.read();
+
+    let dataset = current_dataset.read();
+    let format = dataset.time_format();
+    let names: Vec<&str> = dataset.node_names().collect();
+    let name = |i: usize| names.get(i).copied().unwrap_or_default().to_string();
+
+    rsx! {
+        div { class: "data-output",
+            p { "coverage: {metrics.coverage():.3}" }
+            p { "number of nodes: {metrics.number_of_nodes():.2}" }
+            p { "number of links: {metrics.number_of_links():.2}" }
+            p { "density: {metrics.density():.4}" }
+            p { "average degree: {metrics.average_degree():.3}" }
+            p { "average clustering: {metrics.average_clustering():.3}" }
+        }
+        match hovered.read().as_slice() {
+            [v] => rsx! {
+                div { class: "data-output",
+                    h3 { "{name(*v)}" }
+                    p { "presence: {format.format_duration(metrics.node_presence(*v))}" }
+                    p { "contribution: {metrics.node_contribution(*v):.3}" }
+                    p { "neighbours: {metrics.neighborhood(*v).len()}" }
+                    p { "degree: {metrics.degree(*v):.3}" }
+                    p { "clustering: {metrics.clustering(*v):.3}" }
+                }
+            },
+            [u, v, ..] if u != v => rsx! {
+                div { class: "data-output",
+                    h3 { "{name(*u)} - {name(*v)}" }
+                    p { "presence: {format.format_duration(metrics.link_presence(*u, *v))}" }
+                    p { "contribution: {metrics.link_contribution(*u, *v):.4}" }
+                }
+            },
+            _ => rsx! {},
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use super::cytoscape::*;
+use super::node_style::{COLORS, SHAPES};
 use super::Graph;
 use kurbo::Vec2;
 use serde_json::json;
@@ -53,9 +54,6 @@ impl Graph {
     }
 }
 
-const SHAPES: [&str; 5] = ["diamond", "ellipse", "hexagon", "rectangle", "triangle"];
-const COLORS: [&str; 8] = ["royalblue", "seagreen", "crimson", "yellow", "violet", "navy", "darkorange", "black"];
-
 
 pub fn create_style() -> CytoscapeStyle {
     let mut style_sheet = Vec::new();
@@ -79,7 +77,7 @@ pub fn create_style() -> CytoscapeStyle {
         })
     }
 
-    for s in SHAPES {
+    for s in SHAPES.map(|s| format!("{s:?}").to_lowercase()) {
         style_sheet.push(CytoscapeStyleRule {
             selector: format!(".shape-{s}"),
             style: json!{{
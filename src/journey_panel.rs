@@ -0,0 +1,96 @@
+use crate::graph_data::Highlight;
+use crate::temporal_paths::{Journey, PathKind};
+use crate::LinkStream;
+use dioxus::prelude::*;
+
+/// Journey tree from `source`: every hop of every journey, with reachable
+/// and unreachable nodes told apart.
+pub fn journey_highlight(source: usize, journeys: &[Option<Journey>]) -> Highlight {
+    let mut highlight = Highlight::default();
+    for (i, journey) in journeys.iter().enumerate() {
+        let class = match journey {
+            _ if i == source => "journey-source",
+            Some(_) => "reachable",
+            None => "unreachable",
+        };
+        highlight.node_classes.push((i, class.to_string()));
+        for hop in journey.iter().flatten() {
+            if !highlight.edges.contains(&(hop.from, hop.to)) {
+                highlight.edges.push((hop.from, hop.to));
+            }
+        }
+    }
+    highlight
+}
+
+/// Choice of the kind of journey, and details of the journey to the hovered node.
+#[component]
+pub fn JourneyPanel(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    journey_source: Signal<Option<usize>>,
+    mut path_kind: Signal<PathKind>,
+    journeys: ReadOnlySignal<Option<Vec<Option<Journey>>>>,
+    hovered: ReadOnlySignal<Vec<usize>>,
+) -> Element {
+    let dataset = current_dataset.read();
+    let format = dataset.time_format();
+    let names: Vec<&str> = dataset.node_names().collect();
+    let name = |i: usize| names.get(i).copied().unwrap_or_default().to_string();
+
+    let Some(source) = journey_source() else {
+        return rsx! {
+            div { class: "default-output", "click on a node to choose the source" }
+        };
+    };
+    let journeys = journeys().unwrap_or_default();
+    // the source is not counted
+    let reachable = journeys
+        .iter()
+        .filter(|j| j.is_some())
+        .count()
+        .saturating_sub(1);
+    let others = journeys.len().saturating_sub(1);
+    let target = hovered.read().first().copied().filter(|t| *t != source);
+
+    rsx! {
+        div { class: "style-select",
+            span { "kind" }
+            select {
+                onchange: move |e: Event<FormData>| {
+                    if let Some(k) = PathKind::ALL.into_iter().find(|k| k.label() == e.value()) {
+                        path_kind.set(k)
+                    }
+                },
+                for k in PathKind::ALL {
+                    option { value: k.label(), selected: k == path_kind(), "{k.label()}" }
+                }
+            }
+        }
+        div { class: "data-output",
+            p { "source: {name(source)}" }
+            p { "reachable nodes: {reachable} / {others}" }
+        }
+        match target.map(|t| (t, journeys.get(t).cloned().flatten())) {
+            None => rsx! {
+                div { class: "default-output", "hover a node to see its journey" }
+            },
+            Some((t, None)) => rsx! {
+                div { class: "data-output", "{name(t)} can not be reached" }
+            },
+            Some((t, Some(journey))) => rsx! {
+                div { class: "data-output",
+                    p { "to {name(t)}: {journey.len()} hops" }
+                    if let (Some(first), Some(last)) = (journey.first(), journey.last()) {
+                        p { "arrival: {format.format_time(last.time)}" }
+                        p { "duration: {format.format_duration(last.time - first.time)}" }
+                    }
+                    for hop in journey {
+                        p { class: "hop",
+                            "{name(hop.from)} → {name(hop.to)} at {format.format_time(hop.time)}"
+                        }
+                    }
+                }
+            },
+        }
+    }
+}
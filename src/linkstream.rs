@@ -1,14 +1,44 @@
+use crate::time_format::{TimeFormat, TimeUnit};
 use rust_lapper::{Interval, Lapper};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::ops::Range;
 
 // TODO: utiliser `Interval`
 #[derive(Copy, Clone, Serialize, Deserialize, Debug, PartialEq)]
-struct Link {
-    n1: usize,
-    n2: usize,
-    start: u64,
-    end: u64,
+pub struct Link {
+    pub n1: usize,
+    pub n2: usize,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Value of a node attribute: numbers are treated as a continuous scale,
+/// strings as categories.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum Attribute {
+    Numeric(f64),
+    Categorical(String),
+}
+
+impl Attribute {
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Attribute::Numeric(x) => Some(*x),
+            Attribute::Categorical(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Attribute::Numeric(x) => write!(f, "{x}"),
+            Attribute::Categorical(s) => write!(f, "{s}"),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
@@ -18,6 +48,57 @@ pub struct LinkStreamData {
     links: Vec<Link>,
     min_time: u64,
     max_time: u64,
+    /// attribute name -> one value per node (`null` when unknown)
+    #[serde(default)]
+    node_attributes: BTreeMap<String, Vec<Option<Attribute>>>,
+    #[serde(default)]
+    time_unit: TimeUnit,
+    /// unix time (in seconds) of the time value 0
+    #[serde(default)]
+    epoch: i64,
+    /// time zone used for display, in minutes east of UTC
+    #[serde(default)]
+    utc_offset: i32,
+}
+
+impl LinkStreamData {
+    pub fn new(
+        node_names: Vec<String>,
+        links: Vec<Link>,
+        time_window: Range<u64>,
+        time_unit: TimeUnit,
+    ) -> Self {
+        Self {
+            node_count: node_names.len(),
+            node_names,
+            links,
+            min_time: time_window.start,
+            max_time: time_window.end,
+            time_unit,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_node_attribute(mut self, name: &str, values: Vec<Option<Attribute>>) -> Self {
+        self.node_attributes.insert(name.to_string(), values);
+        self
+    }
+
+    pub fn links(&self) -> &[Link] {
+        &self.links
+    }
+
+    pub fn time_window(&self) -> Range<u64> {
+        self.min_time..self.max_time
+    }
+
+    /// Same nodes, attributes and time window, with other links.
+    pub fn with_links(&self, links: Vec<Link>) -> Self {
+        Self {
+            links,
+            ..self.clone()
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -29,7 +110,12 @@ pub struct LinkStream {
 }
 
 impl LinkStream {
-    pub fn new(name: String, data: LinkStreamData) -> Self {
+    pub fn new(name: String, mut data: LinkStreamData) -> Self {
+        let n = data.node_count;
+        for values in data.node_attributes.values_mut() {
+            values.resize(n, None);
+        }
+
         let intervals = Lapper::new(
             data.links
                 .iter()
@@ -49,7 +135,11 @@ impl LinkStream {
         }
     }
 
-    fn links_during(&self, time_window: Range<u64>) -> impl Iterator<Item = Link> + '_ {
+    pub fn links(&self) -> &[Link] {
+        &self.data.links
+    }
+
+    pub fn links_during(&self, time_window: Range<u64>) -> impl Iterator<Item = Link> + '_ {
         self.intervals
             .find(time_window.start, time_window.end)
             .map(|it| self.data.links[it.val])
@@ -65,16 +155,13 @@ impl LinkStream {
         result
     }
 
-    pub fn interaction_score_during(&self, time_window: Range<u64>) -> f64 {
+    /// Total duration of the links between `nodes`.
+    pub fn interaction_score_during(&self, time_window: Range<u64>, nodes: &[bool]) -> f64 {
         let mut score = 0.;
-        for Link {
-            n1: _,
-            n2: _,
-            start,
-            end,
-        } in self.links_during(time_window)
-        {
-            score += (end - start) as f64;
+        for Link { n1, n2, start, end } in self.links_during(time_window) {
+            if nodes[n1] && nodes[n2] {
+                score += (end - start) as f64;
+            }
         }
         score
     }
@@ -90,4 +177,70 @@ impl LinkStream {
     pub fn time_window(&self) -> Range<u64> {
         self.data.min_time..self.data.max_time
     }
+
+    pub fn time_format(&self) -> TimeFormat {
+        TimeFormat {
+            unit: self.data.time_unit,
+            epoch: self.data.epoch,
+            utc_offset: self.data.utc_offset,
+        }
+    }
+
+    pub fn attribute_names(&self) -> impl Iterator<Item = &str> {
+        self.data.node_attributes.keys().map(|x| x as _)
+    }
+
+    pub fn node_attribute(&self, name: &str) -> Option<&[Option<Attribute>]> {
+        self.data.node_attributes.get(name).map(|x| x as _)
+    }
+
+    /// An attribute is numeric if it has a known value, and every known value is a number.
+    pub fn is_numeric_attribute(&self, name: &str) -> bool {
+        self.node_attribute(name).is_some_and(|values| {
+            let mut known = values.iter().flatten().peekable();
+            known.peek().is_some() && known.all(|v| matches!(v, Attribute::Numeric(_)))
+        })
+    }
+}
+
+/// Small stream for the tests: `links` are `(n1, n2, start, end)` between nodes named `names`.
+#[cfg(test)]
+pub fn test_stream(
+    names: &[&str],
+    links: &[(usize, usize, u64, u64)],
+    time_window: Range<u64>,
+) -> LinkStream {
+    let names = names.iter().map(|name| name.to_string()).collect();
+    let links = links
+        .iter()
+        .map(|&(n1, n2, start, end)| Link { n1, n2, start, end })
+        .collect();
+    let data = LinkStreamData::new(names, links, time_window, TimeUnit::Steps);
+    LinkStream::new("small".to_string(), data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_attributes() {
+        let number = |x: f64| Some(Attribute::Numeric(x));
+        let word = |x: &str| Some(Attribute::Categorical(x.to_string()));
+        let data = LinkStreamData {
+            node_count: 2,
+            node_names: vec!["a".to_string(), "b".to_string()],
+            node_attributes: BTreeMap::from([
+                ("age".to_string(), vec![number(1.), None]),
+                ("class".to_string(), vec![number(1.), word("x")]),
+                ("unknown".to_string(), vec![None, None]),
+            ]),
+            ..Default::default()
+        };
+        let stream = LinkStream::new("small".to_string(), data);
+        assert!(stream.is_numeric_attribute("age"));
+        assert!(!stream.is_numeric_attribute("class"));
+        assert!(!stream.is_numeric_attribute("unknown"));
+        assert!(!stream.is_numeric_attribute("missing"));
+    }
 }
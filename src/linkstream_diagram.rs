@@ -0,0 +1,126 @@
+use crate::communities::CommunityMethod;
+use crate::ordering::NodeOrder;
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::ops::Range;
+
+/// Classic link stream representation: one horizontal line per node, and for
+/// each link a vertical segment between its two nodes at its start time,
+/// followed by a horizontal bar for its duration.
+#[component]
+pub fn LinkStreamDiagram(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    time_window: ReadOnlySignal<Range<u64>>,
+    aggregation_window: ReadOnlySignal<Range<u64>>,
+    time: ReadOnlySignal<u64>,
+    /// used by the community order, Louvain by default
+    community_method: ReadOnlySignal<Option<CommunityMethod>>,
+    width: Option<f64>,
+    height: Option<f64>,
+) -> Element {
+    const LABEL_WIDTH: f64 = 90.;
+    const MARGIN: f64 = 60.;
+    let width = width.unwrap_or(1400.);
+    let height = height.unwrap_or(700.);
+
+    let mut order = use_signal(NodeOrder::default);
+    // not computed again when only the cursor moves
+    let rows = use_memo(move || {
+        let Range { start, end } = time_window();
+        order().order(
+            &current_dataset.read(),
+            start..end,
+            community_method().unwrap_or_default(),
+        )
+    });
+
+    let dataset = current_dataset.read();
+    let Range { start, end } = time_window();
+    let n = dataset.node_count();
+    let names: Vec<&str> = dataset.node_names().collect();
+    let format = dataset.time_format();
+
+    let rows = rows();
+    let mut rank = vec![0; n];
+    for (r, node) in rows.iter().enumerate() {
+        rank[*node] = r;
+    }
+
+    let plot_w = width - LABEL_WIDTH;
+    let row_h = (height - 2. * MARGIN) / n.max(1) as f64;
+    let x = move |t: u64| {
+        let t = t.clamp(start, end);
+        LABEL_WIDTH + (t - start) as f64 / (end - start).max(1) as f64 * plot_w
+    };
+    let y = move |node: usize| MARGIN + (rank[node] as f64 + 0.5) * row_h;
+
+    let links: Vec<_> = dataset.links_during(start..end).collect();
+    let aggregation = aggregation_window();
+
+    rsx! {
+        div { class: "view-container",
+            div { class: "view-options",
+                span { "order by " }
+                select {
+                    onchange: move |e: Event<FormData>| order.set(NodeOrder::from_label(&e.value())),
+                    for o in NodeOrder::ALL {
+                        option { value: o.label(), selected: o == order(), "{o.label()}" }
+                    }
+                }
+            }
+            svg { width, height,
+                rect {
+                    class: "aggregation-window",
+                    x: x(aggregation.start),
+                    y: MARGIN,
+                    width: x(aggregation.end) - x(aggregation.start),
+                    height: height - 2. * MARGIN
+                }
+                for node in rows.iter().copied() {
+                    line {
+                        class: "node-line",
+                        x1: LABEL_WIDTH,
+                        x2: width,
+                        y1: y(node),
+                        y2: y(node)
+                    }
+                    text {
+                        x: LABEL_WIDTH - 4.,
+                        y: y(node) + 3.,
+                        font_size: 10,
+                        text_anchor: "end",
+                        "{names.get(node).copied().unwrap_or_default()}"
+                    }
+                }
+                for link in links {
+                    g { class: "stream-link",
+                        title {
+                            "{names.get(link.n1).copied().unwrap_or_default()} - {names.get(link.n2).copied().unwrap_or_default()}: "
+                            "{format.format_time(link.start)} → {format.format_time(link.end)} "
+                            "({format.format_duration(link.end - link.start)})"
+                        }
+                        line {
+                            x1: x(link.start),
+                            x2: x(link.start),
+                            y1: y(link.n1),
+                            y2: y(link.n2)
+                        }
+                        line {
+                            x1: x(link.start),
+                            x2: x(link.end),
+                            y1: y(link.n1).max(y(link.n2)),
+                            y2: y(link.n1).max(y(link.n2))
+                        }
+                    }
+                }
+                line {
+                    class: "cursor-line",
+                    x1: x(time()),
+                    x2: x(time()),
+                    y1: MARGIN,
+                    y2: height - MARGIN
+                }
+            }
+        }
+    }
+}
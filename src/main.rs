@@ -3,21 +3,68 @@
 use crate::linkstream::{LinkStream, LinkStreamData};
 use crate::utils::Matrix;
 use dioxus::prelude::*;
+use graph_data::{GraphData, Highlight};
 use kurbo::Vec2;
 use std::collections::HashMap;
 use std::ops::Range;
 use tracing::Level;
 
+mod aggregation;
+mod centrality;
+mod cliques;
+mod communities;
+mod components;
+mod distributions;
+mod epidemics;
+mod filters;
 mod force_directed_layout;
+mod generators;
+mod graph_data;
+mod graph_stats;
+mod journey_panel;
 mod linkstream;
+mod linkstream_diagram;
+mod matrix_view;
+mod metrics;
+mod motifs;
+mod node_panel;
+mod node_style;
+mod null_models;
+mod ordering;
+mod pair_panel;
 mod render_graph;
+mod snapshots;
+mod spread_panel;
 mod svg_timeline;
-mod utils;
+mod temporal_centrality;
+mod temporal_paths;
+mod time_format;
 mod time_slider;
+mod timeline_brush;
+mod utils;
 
-
-use svg_timeline::SvgTimeLine;
+use aggregation::{Aggregation, AggregationControl};
+use centrality::{all_centralities, Centrality, CentralityTable};
+use cliques::{CliquePanel, DeltaClique};
+use distributions::DistributionPanel;
+use epidemics::{Epidemic, EpidemicPanel, EpidemicParams};
+use filters::{Filter, FilterPanel};
+use generators::{Generator, GeneratorParams};
+use graph_stats::{StatsPanel, StreamMetricsPanel};
+use journey_panel::JourneyPanel;
+use linkstream_diagram::LinkStreamDiagram;
+use matrix_view::MatrixView;
+use motifs::MotifPanel;
+use node_panel::{NodeCard, NodeSearch};
+use node_style::{NodeMetric, NodeStyle, StyleControl};
+use null_models::NullModel;
+use pair_panel::PairInspector;
 use render_graph::MyGraph;
+use snapshots::SmallMultiples;
+use spread_panel::{Spread, SpreadPanel};
+use svg_timeline::SvgTimeLine;
+use temporal_centrality::TemporalValues;
+use temporal_paths::{Journey, PathKind};
 use time_slider::TimeSlider;
 use utils::Reset;
 
@@ -35,11 +82,99 @@ fn main() {
     launch(Home);
 }
 
+/// What is drawn in the main area of the explorer.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+enum View {
+    #[default]
+    Graph,
+    Stream,
+    /// graph and adjacency matrix side by side
+    Matrix,
+    Snapshots,
+}
+
+impl View {
+    const ALL: [View; 4] = [View::Graph, View::Stream, View::Matrix, View::Snapshots];
+
+    fn label(&self) -> &'static str {
+        match self {
+            View::Graph => "graph",
+            View::Stream => "link stream",
+            View::Matrix => "matrix",
+            View::Snapshots => "snapshots",
+        }
+    }
+}
+
+#[component]
+fn ViewPicker(view: Signal<View>) -> Element {
+    rsx! {
+        select {
+            class: "view-picker",
+            onchange: move |e: Event<FormData>| {
+                if let Some(v) = View::ALL.into_iter().find(|v| v.label() == e.value()) {
+                    view.set(v)
+                }
+            },
+            for v in View::ALL {
+                option { value: v.label(), selected: v == view(), "{v.label()}" }
+            }
+        }
+    }
+}
+
+/// What a click on a node does.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+enum Tool {
+    #[default]
+    Move,
+    /// show the journeys starting from the clicked node
+    Journey,
+    /// propagate an information from the clicked node, from the current time
+    Spread,
+    /// show the connected component of the clicked node
+    Component,
+    /// list the Δ-cliques of the dataset
+    Clique,
+    /// choose the first infected nodes of an epidemic
+    Epidemic,
+}
 
+impl Tool {
+    const ALL: [Tool; 6] = [
+        Tool::Move,
+        Tool::Journey,
+        Tool::Spread,
+        Tool::Component,
+        Tool::Clique,
+        Tool::Epidemic,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Tool::Move => "move",
+            Tool::Journey => "journeys",
+            Tool::Spread => "spread",
+            Tool::Component => "component",
+            Tool::Clique => "cliques",
+            Tool::Epidemic => "epidemic",
+        }
+    }
+}
 
-#[allow(non_snake_case)]
-fn ToolBox() -> Element {
-    rsx! {}
+#[component]
+fn ToolBox(tool: Signal<Tool>) -> Element {
+    rsx! {
+        div { class: "toolbox",
+            for t in Tool::ALL {
+                button {
+                    class: if t == tool() { "tool active" } else { "tool" },
+                    onclick: move |_| tool.set(t),
+                    "{t.label()}"
+                }
+            }
+        }
+    }
 }
 
 #[component]
@@ -63,18 +198,46 @@ fn Menu(
     time_window: Signal<Range<u64>>,
     time: ReadOnlySignal<u64>,
     r_value: Signal<f64>,
+    node_style: Signal<NodeStyle>,
+    filter: Signal<Filter>,
+    positions: Signal<Vec<Vec2>>,
+    selected_node: Signal<Option<usize>>,
+    ego: Signal<bool>,
+    selected_pair: Signal<Option<(usize, usize)>>,
+    aggregation: Signal<Aggregation>,
+    view: Signal<View>,
+    tool: Signal<Tool>,
+    journey_source: Signal<Option<usize>>,
+    path_kind: Signal<PathKind>,
+    journeys: ReadOnlySignal<Option<Vec<Option<Journey>>>>,
+    hovered: Signal<Vec<usize>>,
+    marks: ReadOnlySignal<Vec<u64>>,
+    spread_source: ReadOnlySignal<Option<(usize, u64)>>,
+    spread: ReadOnlySignal<Option<Spread>>,
+    aggregation_window: ReadOnlySignal<Range<u64>>,
+    centralities: ReadOnlySignal<Vec<Vec<f64>>>,
+    focused_clique: Signal<Option<DeltaClique>>,
+    epidemic_params: Signal<EpidemicParams>,
+    epidemic: ReadOnlySignal<Option<Epidemic>>,
 ) -> Element {
     let mut zoom = use_signal(|| 0.);
+    let format = current_dataset.read().time_format();
+    let Range { start, end } = time_window();
 
     rsx! {
         div { class: "menu-container",
             div {
-                span { id: "current-time", class: "current-time", "current time: {time}" }
+                div { id: "current-time", class: "current-time",
+                    p { "current time: {format.format_time(time())}" }
+                    p { "window: {format.format_duration(end - start)}" }
+                    p { "aggregation: {format.format_duration(aggregation().width(&(start..end)))}" }
+                }
+                ViewPicker { view }
             }
             div { class: "right-bar",
                 div { id: "graph-info", class: "graph-info",
                     div { class: "rb-area tools",
-                        ToolBox {}
+                        ToolBox { tool }
                         div { class: "zoom-container",
                             p { class: "zoom-label", "Zoom" }
                             span { "1x" }
@@ -90,9 +253,99 @@ fn Menu(
                             span { "1000x" }
                         }
                     }
+                    div { class: "rb-area nodes",
+                        h2 { "Nodes" }
+                        NodeSearch { current_dataset, selected: selected_node, positions, ego }
+                        NodeCard {
+                            current_dataset,
+                            time_window,
+                            aggregation_window,
+                            selected: selected_node,
+                            hovered
+                        }
+                    }
+                    if selected_pair().is_some() {
+                        div { class: "rb-area pair",
+                            h2 { "Pair" }
+                            PairInspector { current_dataset, time_window, pair: selected_pair }
+                        }
+                    }
+                    if tool() == Tool::Journey {
+                        div { class: "rb-area journeys",
+                            h2 { "Journeys" }
+                            JourneyPanel {
+                                current_dataset,
+                                journey_source,
+                                path_kind,
+                                journeys,
+                                hovered
+                            }
+                        }
+                    }
+                    if tool() == Tool::Spread {
+                        div { class: "rb-area spread",
+                            h2 { "Reachability" }
+                            SpreadPanel {
+                                current_dataset,
+                                time_window,
+                                spread_source,
+                                spread,
+                                hovered,
+                                time,
+                                r_value
+                            }
+                        }
+                    }
+                    if tool() == Tool::Clique {
+                        div { class: "rb-area cliques",
+                            h2 { "Δ-Cliques" }
+                            CliquePanel { current_dataset, time_window, focused: focused_clique }
+                        }
+                    }
+                    if tool() == Tool::Epidemic {
+                        div { class: "rb-area epidemic",
+                            h2 { "Epidemic" }
+                            EpidemicPanel {
+                                current_dataset,
+                                time_window,
+                                params: epidemic_params,
+                                epidemic,
+                                time,
+                                r_value
+                            }
+                        }
+                    }
                     div { class: "rb-area output",
                         h2 { "Graph Stats" }
-                        div { class: "data-output", "TODO" }
+                        StatsPanel { current_dataset, time_window, aggregation_window }
+                    }
+                    div { class: "rb-area centralities",
+                        h2 { "Centralities" }
+                        CentralityTable { current_dataset, values: centralities, hovered }
+                    }
+                    div { class: "rb-area metrics",
+                        h2 { "Stream Metrics" }
+                        StreamMetricsPanel { current_dataset, time_window, hovered }
+                    }
+                    div { class: "rb-area distributions",
+                        h2 { "Contact Times" }
+                        DistributionPanel { current_dataset, hovered }
+                    }
+                    div { class: "rb-area motifs",
+                        h2 { "Temporal Motifs" }
+                        MotifPanel { current_dataset, time_window }
+                    }
+                    div { class: "rb-area aggregation",
+                        h2 { "Aggregation" }
+                        AggregationControl { current_dataset, aggregation, time_window }
+                    }
+                    div { class: "rb-area filters",
+                        h2 { "Filters" }
+                        FilterPanel { current_dataset, filter }
+                    }
+                    div { class: "rb-area style",
+                        h2 { "Node Style" }
+                        StyleControl { current_dataset, style: node_style }
                     }
                 }
             }
@@ -101,7 +354,10 @@ fn Menu(
                 time_window,
                 zoom,
                 time,
-                r_value
+                r_value,
+                marks,
+                aggregation,
+                filter
             }
         }
     }
@@ -114,7 +370,7 @@ fn Popup(children: Vec<VNode>) -> Element {
     }
 }
 
-static DATASETS: [(&'static str, &'static str); 3] = [
+static DATASETS: [(&str, &str); 3] = [
     ("baboon", ("baboon.json")),
     ("school", ("school.json")),
     ("example", ("example.json")),
@@ -130,7 +386,8 @@ async fn load_linkstream_and_compute_positions(
     let n = link_stream.node_count();
 
     let matrix = link_stream.interaction_matrix(link_stream.time_window());
-    let m = matrix.matrix_max();
+    // a stream without links is laid out without attraction
+    let m = matrix.matrix_max().max(f64::MIN_POSITIVE);
     let normalized_matrix = matrix.matrix_map(|x| x / m);
 
     let params = force_directed_layout::ForceDirectedLayoutParams {
@@ -143,55 +400,114 @@ async fn load_linkstream_and_compute_positions(
     };
 
     let positions = force_directed_layout::compute(n, &normalized_matrix, params);
-    return (link_stream, positions);
+    (link_stream, positions)
 }
 
 #[component]
 fn GraphView(
     current_dataset: ReadOnlySignal<LinkStream>,
     time_window: ReadOnlySignal<Range<u64>>,
-    t: ReadOnlySignal<u64>,
-    dt: ReadOnlySignal<u64>,
+    aggregation_window: ReadOnlySignal<Range<u64>>,
     mut positions: Signal<Vec<Vec2>>,
+    node_style: ReadOnlySignal<NodeStyle>,
+    filter: ReadOnlySignal<Filter>,
+    hovered: Signal<Vec<usize>>,
+    highlight: ReadOnlySignal<Highlight>,
+    /// community of each node, overriding the color attribute
+    communities: Option<Vec<usize>>,
+    selected: Option<usize>,
+    /// node whose neighbourhood is shown, the rest being dimmed
+    ego: Option<usize>,
+    on_node_click: Option<EventHandler<usize>>,
+    on_edge_click: Option<EventHandler<(usize, usize)>>,
+    /// every centrality of the aggregation window, see `all_centralities`
+    centralities: Option<ReadOnlySignal<Vec<Vec<f64>>>>,
+    /// temporal centralities of the time window, see `NodeStyle::temporal_centralities`
+    temporal_centralities: Option<ReadOnlySignal<TemporalValues>>,
+    width: Option<i64>,
+    height: Option<i64>,
+    view_box: Option<(f64, f64)>,
 ) -> Element {
     let n = current_dataset.read().node_count();
     let n_pos = positions.read().len();
     assert_eq!(n, n_pos);
 
-    let (edge_weigths, edges) = {
-        let mut edges = Vec::new();
-        let matrix = current_dataset
-            .read()
-            .interaction_matrix(t() - dt() / 2..t() + dt() / 2);
-        let m = matrix.matrix_max();
-
-        for n1 in 0..n {
-            for n2 in 0..n {
-                if matrix[n1][n2] > 0. {
-                    edges.push((n1, n2));
-                }
-            }
+    // only computed again when the metric or its window change
+    let metric_values = move |metric: Option<NodeMetric>| {
+        let metric = metric?;
+        if let (NodeMetric::Static(c), Some(values)) = (metric, centralities) {
+            let column = Centrality::ALL.iter().position(|x| *x == c)?;
+            return values.read().get(column).cloned();
         }
-
-        (matrix.matrix_map(|x| x / m), edges)
-    };
-
-    let node_weigths = {
-        let matrix = current_dataset.read().interaction_matrix(time_window());
-        let node_weigths = matrix.sum_one_level();
-        let m = node_weigths.matrix_max();
-        node_weigths.matrix_map(|x| x / m)
+        if let (NodeMetric::Temporal(c), Some(values)) = (metric, temporal_centralities) {
+            let values = values.read();
+            return values.iter().find(|(x, _)| *x == c).map(|(_, v)| v.clone());
+        }
+        let window = if metric.is_static() {
+            aggregation_window()
+        } else {
+            time_window()
+        };
+        Some(metric.values(&current_dataset.read(), window))
     };
+    let size_values = use_memo(move || metric_values(node_style.read().size_metric));
+    let color_values = use_memo(move || metric_values(node_style.read().color_metric));
+
+    let mut graph = GraphData::new(
+        &current_dataset.read(),
+        time_window(),
+        aggregation_window(),
+        &node_style.read(),
+    );
+    if let Some(values) = size_values() {
+        graph.size_by_metric(&values);
+    }
+    if let Some(values) = color_values() {
+        graph.color_by_metric(&values);
+    }
+    if let Some(labels) = &communities {
+        graph.color_communities(labels);
+    }
+    graph.apply_filter(&current_dataset.read(), &filter.read());
+    if let Some(node) = ego {
+        graph.focus_ego(node);
+    }
+    if let Some(i) = selected {
+        graph.node_classes[i].push("selected".to_string());
+    }
+    for i in hovered() {
+        graph.node_classes[i].push("highlighted".to_string());
+    }
+    for (i, class) in highlight().node_classes {
+        graph.node_classes[i].push(class);
+    }
 
     rsx! {
         MyGraph {
             size: n,
-            names: current_dataset.read().node_names().map(|x| Some(x.to_string())).collect(),
-            node_classes: vec![vec![]; n],
-            node_weights: node_weigths,
-            edge_weights: edge_weigths,
-            edges,
-            positions
+            names: graph.names,
+            node_classes: graph.node_classes,
+            node_shapes: graph.node_shapes,
+            visible: graph.visible.clone(),
+            on_hover: move |id: Option<usize>| hovered.set(id.into_iter().collect()),
+            on_click: move |id| {
+                if let Some(f) = on_node_click {
+                    f.call(id)
+                }
+            },
+            on_edge_click,
+            highlighted_edges: highlight()
+                .edges
+                .into_iter()
+                .filter(|(a, b)| graph.visible[*a] && graph.visible[*b])
+                .collect::<Vec<_>>(),
+            node_weights: graph.node_weights,
+            edge_weights: graph.edge_weights,
+            edges: graph.edges,
+            positions,
+            width,
+            height,
+            view_box
         }
     }
 }
@@ -212,12 +528,15 @@ struct ExplorerProps {
     initial_time_window: ReadOnlySignal<Range<u64>>,
 }
 
-
 fn Explorer(props: ExplorerProps) -> Element {
     let visible_toogle = use_signal(|| false);
     let time_window = use_signal(|| props.initial_time_window.cloned());
     let positions = use_signal(|| props.initial_positions.cloned());
     let r_value = use_signal(|| 0.);
+    let node_style = use_signal(NodeStyle::default);
+    let filter = use_signal(Filter::default);
+    let view = use_signal(View::default);
+    let hovered: Signal<Vec<usize>> = use_signal(Vec::new);
 
     let time = use_memo(move || {
         let Range { start, end } = time_window();
@@ -225,33 +544,269 @@ fn Explorer(props: ExplorerProps) -> Element {
         t as u64
     });
 
-    let dt = use_memo(move || {
-        let Range { start, end } = time_window();
-        (end - start) / 100
+    let aggregation = use_signal(Aggregation::default);
+    let aggregation_window = use_memo(move || aggregation().window_at(time(), &time_window()));
+    // shared by the centrality table and the node style
+    let centralities = use_memo(move || {
+        all_centralities(
+            &props
+                .link_stream
+                .read()
+                .interaction_matrix(aggregation_window()),
+        )
+    });
+    // journeys of the whole window are costly: only computed again when the
+    // window or the shown temporal centralities change
+    let shown_temporal = use_memo(move || node_style.read().temporal_centralities());
+    let temporal_centralities = use_memo(move || {
+        let dataset = props.link_stream.read();
+        shown_temporal()
+            .into_iter()
+            .map(|c| (c, c.compute(&dataset, time_window())))
+            .collect::<TemporalValues>()
+    });
+
+    // labels are matched with the previous ones so that colors stay stable
+    let mut community_labels: Signal<Option<Vec<usize>>> = use_signal(|| None);
+    use_effect(move || {
+        let labels = node_style.read().communities.map(|method| {
+            let matrix = props
+                .link_stream
+                .read()
+                .interaction_matrix(aggregation_window());
+            let labels = method.detect(&matrix);
+            match community_labels.peek().as_ref() {
+                Some(previous) => communities::match_labels(previous, &labels),
+                None => labels,
+            }
+        });
+        community_labels.set(labels);
+    });
+
+    let tool = use_signal(Tool::default);
+    let mut journey_source = use_signal(|| None);
+    let path_kind = use_signal(PathKind::default);
+    // journeys leaving the source at the cursor time
+    let journeys = use_memo(move || {
+        let source = journey_source()?;
+        Some(temporal_paths::journeys(
+            &props.link_stream.read(),
+            source,
+            time()..time_window().end,
+            path_kind(),
+        ))
+    });
+
+    let mut spread_source = use_signal(|| None);
+    let spread = use_memo(move || {
+        let (source, start) = spread_source()?;
+        Some(spread_panel::spread(
+            &props.link_stream.read(),
+            source,
+            start,
+        ))
+    });
+
+    let mut selected_node = use_signal(|| None);
+    let ego = use_signal(|| false);
+    let mut selected_pair = use_signal(|| None);
+
+    let mut component_source = use_signal(|| None);
+    let focused_clique = use_signal(|| None);
+    let mut epidemic_params = use_signal(EpidemicParams::default);
+    let epidemic = use_memo(move || {
+        let params = epidemic_params();
+        (!params.seeds.is_empty()).then(|| epidemics::simulate(&props.link_stream.read(), &params))
+    });
+
+    let highlight = use_memo(move || {
+        let mut highlight = match tool() {
+            Tool::Journey => match (journey_source(), journeys()) {
+                (Some(source), Some(journeys)) => {
+                    journey_panel::journey_highlight(source, &journeys)
+                }
+                _ => Highlight::default(),
+            },
+            Tool::Spread => match (spread_source(), spread()) {
+                (Some((source, _)), Some(spread)) => {
+                    spread_panel::spread_highlight(source, &spread, time())
+                }
+                _ => Highlight::default(),
+            },
+            Tool::Component => match component_source() {
+                Some(source) => {
+                    let matrix = props
+                        .link_stream
+                        .read()
+                        .interaction_matrix(aggregation_window());
+                    components::component_highlight(&matrix, source)
+                }
+                None => Highlight::default(),
+            },
+            Tool::Clique => match focused_clique() {
+                Some(clique) => cliques::clique_highlight(&clique),
+                None => Highlight::default(),
+            },
+            Tool::Epidemic => match epidemic() {
+                Some(epidemic) => {
+                    epidemics::epidemic_highlight(&epidemic_params.read(), &epidemic, time())
+                }
+                None => Highlight::default(),
+            },
+            Tool::Move => Highlight::default(),
+        };
+        // the inspected pair
+        highlight.edges.extend(selected_pair());
+        highlight
+    });
+
+    // hop times of the hovered journey, or of all journeys
+    let marks = use_memo(move || {
+        if tool() != Tool::Journey {
+            return Vec::new();
+        }
+        let Some(journeys) = journeys() else {
+            return Vec::new();
+        };
+        let shown: Vec<&Journey> = match hovered.read().first() {
+            Some(target) => journeys.get(*target).into_iter().flatten().collect(),
+            None => journeys.iter().flatten().collect(),
+        };
+        shown.into_iter().flatten().map(|hop| hop.time).collect()
     });
 
+    let on_node_click = move |id: usize| match tool() {
+        Tool::Journey => journey_source.set(Some(id)),
+        Tool::Spread => spread_source.set(Some((id, time()))),
+        Tool::Component => component_source.set(Some(id)),
+        Tool::Epidemic => {
+            let mut params = epidemic_params.write();
+            if params.seeds.is_empty() {
+                params.start = time();
+            }
+            match params.seeds.iter().position(|s| *s == id) {
+                Some(i) => {
+                    params.seeds.remove(i);
+                }
+                None => params.seeds.push(id),
+            }
+        }
+        Tool::Move => selected_node.set(Some(id)),
+        Tool::Clique => {}
+    };
+
     rsx! {
         Reset {
-            GraphView {
-                current_dataset: props.link_stream,
-                positions,
-                t: time,
-                dt,
-                time_window
+            match view() {
+                View::Graph => rsx! {
+                    GraphView {
+                        current_dataset: props.link_stream,
+                        positions,
+                        aggregation_window,
+                        time_window,
+                        node_style,
+                        filter,
+                        hovered,
+                        highlight,
+                        communities: community_labels(),
+                        selected: selected_node(),
+                        ego: selected_node().filter(|_| ego()),
+                        on_node_click,
+                        on_edge_click: move |p| selected_pair.set(Some(p)),
+                        centralities: ReadOnlySignal::from(centralities),
+                        temporal_centralities: ReadOnlySignal::from(temporal_centralities)
+                    }
+                },
+                View::Matrix => rsx! {
+                    GraphView {
+                        current_dataset: props.link_stream,
+                        positions,
+                        aggregation_window,
+                        time_window,
+                        node_style,
+                        filter,
+                        hovered,
+                        highlight,
+                        communities: community_labels(),
+                        selected: selected_node(),
+                        ego: selected_node().filter(|_| ego()),
+                        on_node_click,
+                        on_edge_click: move |p| selected_pair.set(Some(p)),
+                        centralities: ReadOnlySignal::from(centralities),
+                        temporal_centralities: ReadOnlySignal::from(temporal_centralities)
+                    }
+                    MatrixView {
+                        current_dataset: props.link_stream,
+                        aggregation_window,
+                        hovered,
+                        community_method: node_style.read().communities
+                    }
+                },
+                View::Snapshots => rsx! {
+                    SmallMultiples {
+                        current_dataset: props.link_stream,
+                        time_window,
+                        aggregation,
+                        time,
+                        positions,
+                        node_style,
+                        filter,
+                        hovered,
+                        highlight,
+                        temporal_centralities
+                    }
+                },
+                View::Stream => rsx! {
+                    LinkStreamDiagram {
+                        current_dataset: props.link_stream,
+                        time_window,
+                        aggregation_window,
+                        time,
+                        community_method: node_style.read().communities
+                    }
+                },
             }
             Menu {
                 current_dataset: props.link_stream,
                 visible_toogle,
                 time_window,
                 time,
-                r_value
+                r_value,
+                node_style,
+                filter,
+                positions,
+                selected_node,
+                ego,
+                selected_pair,
+                aggregation,
+                view,
+                tool,
+                journey_source,
+                path_kind,
+                journeys,
+                hovered,
+                marks,
+                spread_source,
+                spread,
+                aggregation_window,
+                centralities,
+                focused_clique,
+                epidemic_params,
+                epidemic
             }
         }
     }
 }
 
 #[component]
-fn App(dataset_name: ReadOnlySignal<String>, dataset_path: ReadOnlySignal<String>) -> Element {
+fn App(
+    dataset_name: ReadOnlySignal<String>,
+    dataset_path: ReadOnlySignal<String>,
+    /// generate the dataset instead of loading it from `dataset_path`
+    generator: ReadOnlySignal<Option<GeneratorParams>>,
+    /// randomize the dataset, with a seed
+    null_model: ReadOnlySignal<Option<(NullModel, u64)>>,
+) -> Element {
     tracing::info!("starting app");
     let mut view = use_signal(|| rsx! {});
 
@@ -259,15 +814,24 @@ fn App(dataset_name: ReadOnlySignal<String>, dataset_path: ReadOnlySignal<String
         *view.write() = rsx! {
             LoadingGif {}
         };
-        let name = dataset_name();
-        let path = dataset_path();
-        let data_text = reqwest::get(format!("{PUBLIC_URL}/{path}"))
-            .await
-            .unwrap()
-            .text()
-            .await
-            .unwrap();
-        let dataset: LinkStreamData = serde_json::from_str(&data_text).unwrap();
+        let mut name = dataset_name();
+        let mut dataset: LinkStreamData = match generator() {
+            Some(params) => params.generate(),
+            None => {
+                let path = dataset_path();
+                let data_text = reqwest::get(format!("{PUBLIC_URL}/{path}"))
+                    .await
+                    .unwrap()
+                    .text()
+                    .await
+                    .unwrap();
+                serde_json::from_str(&data_text).unwrap()
+            }
+        };
+        if let Some((model, seed)) = null_model() {
+            dataset = model.generate(&dataset, seed);
+            name = format!("{name} ({}, seed {seed})", model.label());
+        }
         let (stream, positions) = load_linkstream_and_compute_positions(name, dataset).await;
         let time_window = stream.time_window();
 
@@ -293,6 +857,14 @@ fn Home() -> Element {
     });
 
     let mut current_dataset_name: Signal<Option<String>> = use_signal(|| None);
+    let mut null_model: Signal<Option<NullModel>> = use_signal(|| None);
+    let mut seed = use_signal(|| 0u64);
+    let mut nodes = use_signal(|| 30usize);
+    let mut duration = use_signal(|| 1000u64);
+    let generator = move || {
+        let name = current_dataset_name()?;
+        Generator::ALL.into_iter().find(|g| g.label() == name)
+    };
 
     rsx! {
         div { class: "dropdown-dataset-wrapper",
@@ -308,12 +880,70 @@ fn Home() -> Element {
                         "{name}"
                     }
                 }
+                optgroup { label: "generated",
+                    for g in Generator::ALL {
+                        option { value: g.label(), "{g.label()}" }
+                    }
+                }
+            }
+            if generator().is_some() {
+                input {
+                    class: "seed-input",
+                    r#type: "number",
+                    min: "{generators::NODES.start()}",
+                    max: "{generators::NODES.end()}",
+                    title: "nodes",
+                    value: "{nodes}",
+                    onchange: move |e| {
+                        let n: usize = e.value().parse().unwrap_or(30);
+                        nodes.set(n.clamp(*generators::NODES.start(), *generators::NODES.end()))
+                    }
+                }
+                input {
+                    class: "seed-input",
+                    r#type: "number",
+                    min: "{generators::DURATION.start()}",
+                    max: "{generators::DURATION.end()}",
+                    title: "duration (steps)",
+                    value: "{duration}",
+                    onchange: move |e| {
+                        let d: u64 = e.value().parse().unwrap_or(1000);
+                        duration.set(d.clamp(*generators::DURATION.start(), *generators::DURATION.end()))
+                    }
+                }
+            }
+            select {
+                class: "dropdown-null-model",
+                onchange: move |e: Event<FormData>| {
+                    null_model.set(NullModel::ALL.into_iter().find(|m| m.label() == e.value()))
+                },
+                option { value: "", "original data" }
+                for m in NullModel::ALL {
+                    option { value: m.label(), "{m.label()}" }
+                }
+            }
+            if null_model().is_some() || generator().is_some() {
+                input {
+                    class: "seed-input",
+                    r#type: "number",
+                    min: "0",
+                    title: "seed",
+                    value: "{seed}",
+                    onchange: move |e| seed.set(e.value().parse().unwrap_or(0))
+                }
             }
         }
         match current_dataset_name() {
             Some(name) => rsx! {App {
                 dataset_name: name.clone(),
-                dataset_path: dataset_paths.read().get(&name).unwrap().to_string()
+                dataset_path: dataset_paths.read().get(&name).map(|p| p.to_string()).unwrap_or_default(),
+                generator: generator().map(|model| GeneratorParams {
+                    model,
+                    nodes: nodes(),
+                    duration: duration(),
+                    seed: seed(),
+                }),
+                null_model: null_model().map(|m| (m, seed()))
             }},
             None => rsx!{InitialView {}}
         }
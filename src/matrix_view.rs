@@ -0,0 +1,105 @@
+use crate::communities::CommunityMethod;
+use crate::ordering::NodeOrder;
+use crate::utils::Matrix;
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::ops::Range;
+
+/// Heatmap of the interaction matrix of the aggregation window.
+/// Hovering a cell highlights its two nodes in `hovered`.
+#[component]
+pub fn MatrixView(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    aggregation_window: ReadOnlySignal<Range<u64>>,
+    hovered: Signal<Vec<usize>>,
+    /// used by the community order, Louvain by default
+    community_method: ReadOnlySignal<Option<CommunityMethod>>,
+    size: Option<f64>,
+) -> Element {
+    const LABEL_WIDTH: f64 = 70.;
+    let size = size.unwrap_or(650.);
+
+    let mut order = use_signal(|| NodeOrder::CuthillMcKee);
+    // not computed again on hover
+    let matrix = use_memo(move || {
+        current_dataset
+            .read()
+            .interaction_matrix(aggregation_window())
+    });
+    let rows = use_memo(move || {
+        let method = community_method().unwrap_or_default();
+        order().order(&current_dataset.read(), aggregation_window(), method)
+    });
+
+    let dataset = current_dataset.read();
+    let names: Vec<&str> = dataset.node_names().collect();
+    let matrix = matrix.read();
+    let m = matrix.matrix_max().max(f64::MIN_POSITIVE);
+    let rows = rows();
+
+    let cell = (size - LABEL_WIDTH) / rows.len().max(1) as f64;
+    let font_size = cell.clamp(4., 10.);
+    let highlighted = hovered();
+
+    rsx! {
+        div { class: "matrix-view",
+            div { class: "view-options",
+                span { "order by " }
+                select {
+                    onchange: move |e: Event<FormData>| order.set(NodeOrder::from_label(&e.value())),
+                    for o in NodeOrder::ALL {
+                        option { value: o.label(), selected: o == order(), "{o.label()}" }
+                    }
+                }
+            }
+            svg {
+                width: size,
+                height: size,
+                onmouseleave: move |_| hovered.set(vec![]),
+                for (r , i) in rows.iter().copied().enumerate() {
+                    if highlighted.contains(&i) {
+                        rect {
+                            class: "highlight-band",
+                            x: LABEL_WIDTH,
+                            y: LABEL_WIDTH + r as f64 * cell,
+                            width: size - LABEL_WIDTH,
+                            height: cell
+                        }
+                        rect {
+                            class: "highlight-band",
+                            x: LABEL_WIDTH + r as f64 * cell,
+                            y: LABEL_WIDTH,
+                            width: cell,
+                            height: size - LABEL_WIDTH
+                        }
+                    }
+                }
+                for (r , i) in rows.iter().copied().enumerate() {
+                    text {
+                        class: if highlighted.contains(&i) { "matrix-label highlighted" } else { "matrix-label" },
+                        x: LABEL_WIDTH - 4.,
+                        y: LABEL_WIDTH + (r as f64 + 0.8) * cell,
+                        font_size,
+                        text_anchor: "end",
+                        onmouseenter: move |_| hovered.set(vec![i]),
+                        "{names.get(i).copied().unwrap_or_default()}"
+                    }
+                    for (c , j) in rows.iter().copied().enumerate() {
+                        if matrix[i][j] > 0. {
+                            rect {
+                                class: "matrix-cell",
+                                x: LABEL_WIDTH + c as f64 * cell,
+                                y: LABEL_WIDTH + r as f64 * cell,
+                                width: cell,
+                                height: cell,
+                                fill_opacity: matrix[i][j] / m,
+                                onmouseenter: move |_| hovered.set(vec![i, j]),
+                                title { "{names.get(i).copied().unwrap_or_default()} - {names.get(j).copied().unwrap_or_default()}: {matrix[i][j]}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
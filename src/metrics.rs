@@ -0,0 +1,266 @@
+//! Link stream measures from "Stream graphs and link streams for the modeling
+//! of interactions over time" (Latapy, Viard & Magnien, 2018).
+//!
+//! A link stream is a stream graph in which every node is present during the
+//! whole time window, so node presences are all equal to the window duration.
+//! Self loops are ignored: links are unordered pairs of distinct nodes.
+
+use crate::LinkStream;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// Sorted disjoint time intervals.
+pub type Intervals = Vec<Range<u64>>;
+
+fn merge(mut intervals: Vec<Range<u64>>) -> Intervals {
+    intervals.sort_by_key(|i| i.start);
+    let mut result: Intervals = Vec::new();
+    for i in intervals {
+        match result.last_mut() {
+            Some(last) if i.start <= last.end => last.end = last.end.max(i.end),
+            _ => result.push(i),
+        }
+    }
+    result
+}
+
+fn intersection(a: &[Range<u64>], b: &[Range<u64>]) -> Intervals {
+    let (mut i, mut j) = (0, 0);
+    let mut result = Vec::new();
+    while i < a.len() && j < b.len() {
+        let start = a[i].start.max(b[j].start);
+        let end = a[i].end.min(b[j].end);
+        if start < end {
+            result.push(start..end);
+        }
+        if a[i].end < b[j].end {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+fn measure(intervals: &[Range<u64>]) -> u64 {
+    intervals.iter().map(|i| i.end - i.start).sum()
+}
+
+/// Presence intervals of every link of a stream, clipped to a time window.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StreamMetrics {
+    node_count: usize,
+    duration: u64,
+    /// `T_uv` for `u < v`
+    links: BTreeMap<(usize, usize), Intervals>,
+}
+
+impl StreamMetrics {
+    pub fn new(stream: &LinkStream, time_window: Range<u64>) -> Self {
+        let mut links: BTreeMap<(usize, usize), Vec<Range<u64>>> = BTreeMap::new();
+        for link in stream.links_during(time_window.clone()) {
+            let start = link.start.max(time_window.start);
+            let end = link.end.min(time_window.end);
+            if link.n1 == link.n2 || start >= end {
+                continue;
+            }
+            let key = (link.n1.min(link.n2), link.n1.max(link.n2));
+            links.entry(key).or_default().push(start..end);
+        }
+        Self {
+            node_count: stream.node_count(),
+            duration: time_window.end - time_window.start,
+            links: links.into_iter().map(|(k, v)| (k, merge(v))).collect(),
+        }
+    }
+
+    fn times(&self, u: usize, v: usize) -> &[Range<u64>] {
+        self.links
+            .get(&(u.min(v), u.max(v)))
+            .map_or(&[], |x| x.as_slice())
+    }
+
+    /// |T_v|
+    pub fn node_presence(&self, _v: usize) -> u64 {
+        self.duration
+    }
+
+    /// |T_uv|
+    pub fn link_presence(&self, u: usize, v: usize) -> u64 {
+        measure(self.times(u, v))
+    }
+
+    /// |W| / (|T| |V|)
+    pub fn coverage(&self) -> f64 {
+        let total: u64 = (0..self.node_count).map(|v| self.node_presence(v)).sum();
+        total as f64 / (self.duration as f64 * self.node_count as f64)
+    }
+
+    /// n_v = |T_v| / |T|
+    pub fn node_contribution(&self, v: usize) -> f64 {
+        self.node_presence(v) as f64 / self.duration as f64
+    }
+
+    /// m_uv = |T_uv| / |T|
+    pub fn link_contribution(&self, u: usize, v: usize) -> f64 {
+        self.link_presence(u, v) as f64 / self.duration as f64
+    }
+
+    /// N, the sum of node contributions
+    pub fn number_of_nodes(&self) -> f64 {
+        (0..self.node_count)
+            .map(|v| self.node_contribution(v))
+            .sum()
+    }
+
+    /// M, the sum of link contributions
+    pub fn number_of_links(&self) -> f64 {
+        let total: u64 = self.links.values().map(|t| measure(t)).sum();
+        total as f64 / self.duration as f64
+    }
+
+    /// |E| / sum over pairs of |T_u ∩ T_v|
+    pub fn density(&self) -> f64 {
+        let n = self.node_count;
+        if n < 2 {
+            return 0.;
+        }
+        let total: u64 = self.links.values().map(|t| measure(t)).sum();
+        total as f64 / (self.duration as f64 * (n * (n - 1) / 2) as f64)
+    }
+
+    /// N(v): every neighbour of `v` with the times it is linked to `v`
+    pub fn neighborhood(&self, v: usize) -> Vec<(usize, &[Range<u64>])> {
+        (0..self.node_count)
+            .filter(|u| *u != v)
+            .map(|u| (u, self.times(u, v)))
+            .filter(|(_, t)| !t.is_empty())
+            .collect()
+    }
+
+    /// d(v) = |N(v)| / |T|
+    pub fn degree(&self, v: usize) -> f64 {
+        let size: u64 = self.neighborhood(v).iter().map(|(_, t)| measure(t)).sum();
+        size as f64 / self.duration as f64
+    }
+
+    /// degrees weighted by node presence
+    pub fn average_degree(&self) -> f64 {
+        let total: f64 = (0..self.node_count)
+            .map(|v| self.node_contribution(v) * self.degree(v))
+            .sum();
+        total / self.number_of_nodes()
+    }
+
+    /// fraction of the time during which two neighbours of `v` are linked together,
+    /// among the time during which they are both neighbours of `v`
+    pub fn clustering(&self, v: usize) -> f64 {
+        let neighborhood = self.neighborhood(v);
+        let (mut triangles, mut pairs) = (0, 0);
+        for (i, (u, tu)) in neighborhood.iter().enumerate() {
+            for (w, tw) in &neighborhood[i + 1..] {
+                let both = intersection(tu, tw);
+                pairs += measure(&both);
+                triangles += measure(&intersection(&both, self.times(*u, *w)));
+            }
+        }
+        if pairs == 0 {
+            0.
+        } else {
+            triangles as f64 / pairs as f64
+        }
+    }
+
+    /// clustering coefficients weighted by node presence
+    pub fn average_clustering(&self) -> f64 {
+        let total: f64 = (0..self.node_count)
+            .map(|v| self.node_contribution(v) * self.clustering(v))
+            .sum();
+        total / self.number_of_nodes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linkstream::{test_stream, LinkStreamData};
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    // a-b during [0,4] and [6,9], b-c during [2,8], a-c during [3,5], c-d during [1,2]
+    fn small_stream() -> LinkStream {
+        test_stream(
+            &["a", "b", "c", "d"],
+            &[
+                (0, 1, 0, 4),
+                (1, 0, 6, 9),
+                (1, 2, 2, 8),
+                (0, 2, 3, 5),
+                (2, 3, 1, 2),
+                (3, 3, 0, 10),
+            ],
+            0..10,
+        )
+    }
+
+    #[test]
+    fn presence_and_contributions() {
+        let stream = small_stream();
+        let m = StreamMetrics::new(&stream, stream.time_window());
+        assert_eq!(m.node_presence(0), 10);
+        assert_eq!(m.link_presence(0, 1), 7);
+        assert_eq!(m.link_presence(2, 1), 6);
+        assert_eq!(m.link_presence(0, 3), 0);
+        assert!(close(m.coverage(), 1.));
+        assert!(close(m.link_contribution(0, 1), 0.7));
+        assert!(close(m.number_of_nodes(), 4.));
+        assert!(close(m.number_of_links(), 1.6));
+    }
+
+    #[test]
+    fn density_and_degrees() {
+        let stream = small_stream();
+        let m = StreamMetrics::new(&stream, stream.time_window());
+        assert!(close(m.density(), 16. / 60.));
+        assert!(close(m.degree(0), 0.9));
+        assert!(close(m.degree(1), 1.3));
+        assert!(close(m.degree(2), 0.9));
+        assert!(close(m.degree(3), 0.1));
+        assert!(close(m.average_degree(), 0.8));
+        assert_eq!(m.neighborhood(2).len(), 3);
+    }
+
+    #[test]
+    fn clustering() {
+        let stream = small_stream();
+        let m = StreamMetrics::new(&stream, stream.time_window());
+        assert!(close(m.clustering(0), 1.));
+        assert!(close(m.clustering(1), 0.25));
+        assert!(close(m.clustering(2), 0.5));
+        assert!(close(m.clustering(3), 0.));
+        assert!(close(m.average_clustering(), 1.75 / 4.));
+    }
+
+    #[test]
+    fn clipped_to_window() {
+        let stream = small_stream();
+        let m = StreamMetrics::new(&stream, 3..6);
+        assert_eq!(m.link_presence(0, 1), 1);
+        assert_eq!(m.link_presence(1, 2), 3);
+        assert!(close(m.density(), 6. / 18.));
+    }
+
+    #[test]
+    fn example_dataset() {
+        let data: LinkStreamData =
+            serde_json::from_str(include_str!("../assets/example.json")).unwrap();
+        let stream = LinkStream::new("example".to_string(), data);
+        let m = StreamMetrics::new(&stream, stream.time_window());
+        assert!(close(m.coverage(), 1.));
+        assert!(close(m.number_of_links(), 670900. / 100800.));
+        assert!(close(m.density(), 670900. / (100800. * 190.)));
+        assert!(close(m.average_degree(), 2. * m.number_of_links() / 20.));
+    }
+}
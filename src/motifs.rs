@@ -0,0 +1,207 @@
+//! δ-temporal motifs with three edges, from "Motifs in temporal networks"
+//! (Paranjape, Benson & Leskovec, 2017).
+//!
+//! A link is an edge from `n1` to `n2` at its start time. Three links form a
+//! motif instance if they span at most δ and their static graph is connected,
+//! on two or three nodes. As in the paper, the edges of an instance have
+//! strictly increasing times: links starting at the same time are never in
+//! the same instance.
+
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::ops::Range;
+
+/// Three edges between nodes numbered in order of appearance.
+pub type Motif = [(usize, usize); 3];
+
+/// Directed edges between three nodes.
+const PAIRS: [(usize, usize); 6] = [(0, 1), (1, 0), (0, 2), (2, 0), (1, 2), (2, 1)];
+
+/// The 36 motifs: the first edge is `0 → 1`, and the two others are any
+/// edges between `0`, `1` and `2`, node `2` being the third node to appear.
+pub fn motifs() -> Vec<Motif> {
+    PAIRS
+        .iter()
+        .flat_map(|second| PAIRS.iter().map(move |third| [(0, 1), *second, *third]))
+        .collect()
+}
+
+/// `a→b, b→c, a→c`
+pub fn motif_label(motif: &Motif) -> String {
+    let name = |u: usize| ["a", "b", "c"][u];
+    motif
+        .iter()
+        .map(|(u, v)| format!("{}→{}", name(*u), name(*v)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Index in `motifs()` of the motif formed by three edges,
+/// if they are on at most three nodes.
+fn classify(edges: [(usize, usize); 3]) -> Option<usize> {
+    let mut nodes: Vec<usize> = Vec::with_capacity(3);
+    let mut id = |u: usize| match nodes.iter().position(|x| *x == u) {
+        Some(i) => Some(i),
+        None if nodes.len() < 3 => {
+            nodes.push(u);
+            Some(nodes.len() - 1)
+        }
+        None => None,
+    };
+    let mut motif = [(0, 0); 3];
+    for (k, (a, b)) in edges.into_iter().enumerate() {
+        motif[k] = (id(a)?, id(b)?);
+    }
+    let position = |e| PAIRS.iter().position(|p| *p == e);
+    Some(6 * position(motif[1])? + position(motif[2])?)
+}
+
+/// Count of each motif of `motifs()` among the links starting during
+/// `time_window`, each instance spanning at most `delta`.
+pub fn motif_counts(stream: &LinkStream, time_window: Range<u64>, delta: u64) -> Vec<u64> {
+    let mut links: Vec<(u64, usize, usize)> = stream
+        .links_during(time_window.clone())
+        .filter(|l| l.n1 != l.n2 && time_window.contains(&l.start))
+        .map(|l| (l.start, l.n1, l.n2))
+        .collect();
+    links.sort_by_key(|l| l.0);
+
+    let mut counts = vec![0; PAIRS.len() * PAIRS.len()];
+    // first link starting after `t`
+    let after = |t: u64| links.partition_point(|l| l.0 <= t);
+    for (t1, a1, b1) in &links {
+        let end = after(t1 + delta);
+        for (t2, a2, b2) in &links[after(*t1).min(end)..end] {
+            // a second edge away from the first one makes four nodes
+            if ![a2, b2].iter().any(|u| *u == a1 || *u == b1) {
+                continue;
+            }
+            for (_, a3, b3) in &links[after(*t2).min(end)..end] {
+                if let Some(k) = classify([(*a1, *b1), (*a2, *b2), (*a3, *b3)]) {
+                    counts[k] += 1;
+                }
+            }
+        }
+    }
+    counts
+}
+
+/// Histogram of the motifs of the time window, for a choice of δ.
+#[component]
+pub fn MotifPanel(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    time_window: ReadOnlySignal<Range<u64>>,
+) -> Element {
+    // None: 1% of the time window
+    let mut delta: Signal<Option<u64>> = use_signal(|| None);
+    let delta_at =
+        move |window: &Range<u64>| delta().unwrap_or(((window.end - window.start) / 100).max(1));
+    let counts = use_memo(move || {
+        let window = time_window();
+        motif_counts(&current_dataset.read(), window.clone(), delta_at(&window))
+    });
+
+    let format = current_dataset.read().time_format();
+    let Range { start, end } = current_dataset.read().time_window();
+    let deltas: Vec<u64> = format
+        .nice_durations()
+        .take_while(|d| *d < end - start)
+        .collect();
+    let counts = counts();
+    let total: u64 = counts.iter().sum();
+    let max = counts.iter().copied().max().unwrap_or(0).max(1) as f64;
+
+    rsx! {
+        div { class: "style-select",
+            span { "δ" }
+            select {
+                onchange: move |e: Event<FormData>| delta.set(e.value().parse().ok()),
+                option { value: "", selected: delta().is_none(),
+                    "auto ({format.format_duration(delta_at(&time_window()))})"
+                }
+                for d in deltas {
+                    option { value: "{d}", selected: delta() == Some(d), "{format.format_duration(d)}" }
+                }
+            }
+        }
+        div { class: "data-output",
+            p { "{total} motif instances" }
+        }
+        svg {
+            class: "motif-histogram",
+            preserve_aspect_ratio: "none",
+            view_box: "0 0 36 10",
+            width: "100%",
+            for (i , (motif , count)) in motifs().iter().zip(counts).enumerate() {
+                rect {
+                    class: "motif-bar",
+                    x: i as f64 + 0.1,
+                    y: 10. - 10. * count as f64 / max,
+                    width: 0.8,
+                    height: 10. * count as f64 / max,
+                    title { "{motif_label(motif)}: {count}" }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linkstream::test_stream;
+
+    /// Links `(time, n1, n2)` between a, b and c, each lasting 1.
+    fn stream(links: &[(u64, usize, usize)]) -> LinkStream {
+        let links: Vec<_> = links
+            .iter()
+            .map(|&(t, n1, n2)| (n1, n2, t, t + 1))
+            .collect();
+        test_stream(&["a", "b", "c"], &links, 0..10)
+    }
+
+    fn index(motif: Motif) -> usize {
+        motifs().iter().position(|m| *m == motif).unwrap()
+    }
+
+    fn found(counts: &[u64]) -> Vec<(usize, u64)> {
+        counts
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, c)| *c > 0)
+            .collect()
+    }
+
+    #[test]
+    fn triangle_and_ping_pong() {
+        let counts = motif_counts(&stream(&[(0, 0, 1), (1, 1, 2), (2, 0, 2)]), 0..10, 10);
+        assert_eq!(found(&counts), vec![(index([(0, 1), (1, 2), (0, 2)]), 1)]);
+        let counts = motif_counts(&stream(&[(0, 0, 1), (1, 1, 0), (2, 0, 1)]), 0..10, 10);
+        assert_eq!(found(&counts), vec![(index([(0, 1), (1, 0), (0, 1)]), 1)]);
+    }
+
+    #[test]
+    fn edges_at_the_same_time_are_not_ordered() {
+        // a→b, then b→c and a→c at the same time, then a→c:
+        // only a→b, b→c, a→c and a→b, a→c, a→c have increasing times
+        let counts = motif_counts(
+            &stream(&[(0, 0, 1), (1, 1, 2), (1, 0, 2), (2, 0, 2)]),
+            0..10,
+            10,
+        );
+        let mut expected = vec![
+            (index([(0, 1), (1, 2), (0, 2)]), 1),
+            (index([(0, 1), (0, 2), (0, 2)]), 1),
+        ];
+        expected.sort();
+        assert_eq!(found(&counts), expected);
+    }
+
+    #[test]
+    fn instances_span_at_most_delta() {
+        let links = [(0, 0, 1), (1, 1, 2), (2, 0, 2)];
+        assert_eq!(found(&motif_counts(&stream(&links), 0..10, 1)), vec![]);
+        assert_eq!(found(&motif_counts(&stream(&links), 0..10, 2)).len(), 1);
+    }
+}
@@ -0,0 +1,160 @@
+//! Finding a node by name, and details about the selected node.
+
+use crate::LinkStream;
+use dioxus::prelude::*;
+use kurbo::Vec2;
+use std::ops::Range;
+
+/// number of bins of the activity sparkline
+const BINS: u64 = 50;
+/// number of partners listed in the node card
+const PARTNERS: usize = 10;
+/// middle of the default graph drawing, see `render_graph::MyGraph`
+const CENTER: Vec2 = Vec2::new(750., 375.);
+
+/// Time spent by `node` in contact with any other node, in each of `BINS`
+/// slices of the time window.
+pub fn activity(stream: &LinkStream, node: usize, time_window: Range<u64>) -> Vec<u64> {
+    let Range { start, end } = time_window;
+    let step = ((end - start) / BINS).max(1);
+    (start..end)
+        .step_by(step as usize)
+        .map(|t| {
+            let bin = t..(t + step).min(end);
+            stream
+                .links_during(bin.clone())
+                .filter(|l| l.n1 == node || l.n2 == node)
+                .map(|l| l.end.min(bin.end) - l.start.max(bin.start))
+                .sum()
+        })
+        .collect()
+}
+
+/// Other nodes by decreasing interaction time with `node` in the window.
+pub fn partners(stream: &LinkStream, node: usize, window: Range<u64>) -> Vec<(usize, f64)> {
+    let matrix = stream.interaction_matrix(window);
+    let mut result: Vec<(usize, f64)> = matrix[node]
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(j, w)| *j != node && *w > 0.)
+        .collect();
+    result.sort_by(|a, b| b.1.total_cmp(&a.1));
+    result
+}
+
+/// Search box with autocomplete over the node names. Choosing a node selects
+/// it and moves the drawing so that it is in the middle.
+#[component]
+pub fn NodeSearch(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    mut selected: Signal<Option<usize>>,
+    mut positions: Signal<Vec<Vec2>>,
+    mut ego: Signal<bool>,
+) -> Element {
+    let dataset = current_dataset.read();
+    let names: Vec<String> = dataset.node_names().map(|x| x.to_string()).collect();
+    let current = selected().map(|i| names[i].clone()).unwrap_or_default();
+
+    rsx! {
+        div { class: "style-select",
+            span { "search" }
+            input {
+                class: "node-search",
+                list: "node-names",
+                placeholder: "node name",
+                value: "{current}",
+                oninput: move |e| {
+                    let names: Vec<String> = current_dataset
+                        .read()
+                        .node_names()
+                        .map(|x| x.to_string())
+                        .collect();
+                    if let Some(i) = names.iter().position(|name| *name == e.value()) {
+                        let shift = CENTER - positions.read()[i];
+                        for p in positions.write().iter_mut() {
+                            *p += shift;
+                        }
+                        selected.set(Some(i));
+                    }
+                }
+            }
+            datalist { id: "node-names",
+                for name in names {
+                    option { value: "{name}" }
+                }
+            }
+        }
+        div { class: "style-select",
+            span { "ego network" }
+            input {
+                r#type: "checkbox",
+                checked: ego(),
+                onchange: move |e| ego.set(e.checked())
+            }
+            button { onclick: move |_| selected.set(None), "clear" }
+        }
+    }
+}
+
+/// Activity of the selected node over the time window, and its main
+/// partners in the aggregation window.
+#[component]
+pub fn NodeCard(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    time_window: ReadOnlySignal<Range<u64>>,
+    aggregation_window: ReadOnlySignal<Range<u64>>,
+    selected: ReadOnlySignal<Option<usize>>,
+    mut hovered: Signal<Vec<usize>>,
+) -> Element {
+    let activity = use_memo(move || {
+        let node = selected()?;
+        Some(activity(&current_dataset.read(), node, time_window()))
+    });
+
+    let Some(node) = selected() else {
+        return rsx! {
+            div { class: "default-output", "search a node or click on it with the move tool" }
+        };
+    };
+    let dataset = current_dataset.read();
+    let format = dataset.time_format();
+    let names: Vec<&str> = dataset.node_names().collect();
+    let partners = partners(&dataset, node, aggregation_window());
+    let activity = activity().unwrap_or_default();
+    let max = activity.iter().copied().max().unwrap_or(0).max(1);
+    let total: u64 = activity.iter().sum();
+    let points: Vec<String> = activity
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            let x = i as f64 / (activity.len().max(2) - 1) as f64 * 100.;
+            format!("{x},{}", 20. - 20. * *a as f64 / max as f64)
+        })
+        .collect();
+
+    rsx! {
+        div { class: "data-output",
+            p { class: "node-name", "{names[node]}" }
+            p { "{partners.len()} partners, {format.format_duration(total)} of contact in the time window" }
+        }
+        svg {
+            class: "sparkline",
+            preserve_aspect_ratio: "none",
+            view_box: "0 0 100 20",
+            width: "100%",
+            polyline { class: "activity", points: points.join(" ") }
+        }
+        table { class: "partner-table",
+            for (j , w) in partners.into_iter().take(PARTNERS) {
+                tr {
+                    class: "partner-row",
+                    onmouseenter: move |_| hovered.set(vec![j]),
+                    onmouseleave: move |_| hovered.set(vec![]),
+                    td { "{names[j]}" }
+                    td { "{format.format_duration(w as u64)}" }
+                }
+            }
+        }
+    }
+}
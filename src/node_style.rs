@@ -0,0 +1,401 @@
+use crate::centrality::Centrality;
+use crate::communities::CommunityMethod;
+use crate::linkstream::{Attribute, LinkStream};
+use crate::render_graph::NodeShape;
+use crate::temporal_centrality::TemporalCentrality;
+use dioxus::prelude::*;
+use kurbo::Vec2;
+use std::ops::Range;
+
+pub const COLORS: [&str; 8] = [
+    "royalblue",
+    "seagreen",
+    "crimson",
+    "yellow",
+    "violet",
+    "navy",
+    "darkorange",
+    "black",
+];
+
+pub const SHAPES: [NodeShape; 5] = [
+    NodeShape::Ellipse,
+    NodeShape::Diamond,
+    NodeShape::Hexagon,
+    NodeShape::Rectangle,
+    NodeShape::Triangle,
+];
+
+/// Sequential scale of `metric_color_classes`, from low to high values.
+pub const SCALE: [&str; 5] = [
+    "lightsteelblue",
+    "cornflowerblue",
+    "royalblue",
+    "mediumblue",
+    "midnightblue",
+];
+
+/// Node measure computed from the stream, used like a numeric attribute.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum NodeMetric {
+    /// of the graph aggregated over the aggregation window
+    Static(Centrality),
+    /// of the link stream during the time window
+    Temporal(TemporalCentrality),
+}
+
+impl NodeMetric {
+    pub fn all() -> Vec<NodeMetric> {
+        let statics = Centrality::ALL.into_iter().map(NodeMetric::Static);
+        let temporals = TemporalCentrality::ALL
+            .into_iter()
+            .map(NodeMetric::Temporal);
+        statics.chain(temporals).collect()
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NodeMetric::Static(c) => c.label(),
+            NodeMetric::Temporal(c) => c.label(),
+        }
+    }
+
+    pub fn is_static(&self) -> bool {
+        matches!(self, NodeMetric::Static(_))
+    }
+
+    /// Values during `window`, which should be the aggregation window for
+    /// static metrics and the time window for temporal ones.
+    pub fn values(&self, stream: &LinkStream, window: Range<u64>) -> Vec<f64> {
+        match self {
+            NodeMetric::Static(c) => c.compute(&stream.interaction_matrix(window)),
+            NodeMetric::Temporal(c) => c.compute(stream, window),
+        }
+    }
+}
+
+/// Which node attribute drives which visual channel.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct NodeStyle {
+    pub color_by: Option<String>,
+    pub shape_by: Option<String>,
+    pub size_by: Option<String>,
+    /// size nodes by a measure of the time window, instead of `size_by`
+    pub size_metric: Option<NodeMetric>,
+    /// color nodes on a sequential scale, instead of `color_by`
+    pub color_metric: Option<NodeMetric>,
+    /// color by communities of the aggregated graph, instead of `color_by`
+    pub communities: Option<CommunityMethod>,
+}
+
+impl NodeStyle {
+    /// Distinct temporal centralities shown by the size or the color.
+    pub fn temporal_centralities(&self) -> Vec<TemporalCentrality> {
+        let mut result = Vec::new();
+        for metric in [self.size_metric, self.color_metric].into_iter().flatten() {
+            if let NodeMetric::Temporal(c) = metric {
+                if !result.contains(&c) {
+                    result.push(c);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Sorted distinct values of a categorical attribute.
+pub fn categories(values: &[Option<Attribute>]) -> Vec<String> {
+    let mut result: Vec<String> = values.iter().flatten().map(|v| v.to_string()).collect();
+    result.sort();
+    result.dedup();
+    result
+}
+
+fn category_index(values: &[Option<Attribute>]) -> Vec<Option<usize>> {
+    let categories = categories(values);
+    values
+        .iter()
+        .map(|v| {
+            v.as_ref()
+                .and_then(|v| categories.iter().position(|c| *c == v.to_string()))
+        })
+        .collect()
+}
+
+/// `color-<name>` class of each node for community labels.
+pub fn community_color_classes(labels: &[usize]) -> Vec<Vec<String>> {
+    labels
+        .iter()
+        .map(|l| vec![format!("color-{}", COLORS[l % COLORS.len()])])
+        .collect()
+}
+
+/// `color-<name>` class of each node, empty if the value is unknown.
+pub fn node_color_classes(values: &[Option<Attribute>]) -> Vec<Vec<String>> {
+    category_index(values)
+        .into_iter()
+        .map(|i| {
+            i.map(|i| vec![format!("color-{}", COLORS[i % COLORS.len()])])
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+pub fn node_shapes(values: &[Option<Attribute>]) -> Vec<NodeShape> {
+    category_index(values)
+        .into_iter()
+        .map(|i| i.map_or(NodeShape::Ellipse, |i| SHAPES[i % SHAPES.len()]))
+        .collect()
+}
+
+/// Node radius factor between 0.2 and 1, proportional to the attribute.
+pub fn node_sizes(values: &[Option<Attribute>]) -> Vec<f64> {
+    let numbers: Vec<f64> = values.iter().flatten().filter_map(|v| v.as_f64()).collect();
+    let min = numbers.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|v| match v.as_ref().and_then(|v| v.as_f64()) {
+            Some(x) if max > min => 0.2 + 0.8 * (x - min) / (max - min),
+            Some(_) => 1.,
+            None => 0.2,
+        })
+        .collect()
+}
+
+/// Node radius factor between 0.2 and 1, proportional to the measure.
+pub fn metric_sizes(values: &[f64]) -> Vec<f64> {
+    let values: Vec<Option<Attribute>> = values
+        .iter()
+        .map(|x| Some(Attribute::Numeric(*x)))
+        .collect();
+    node_sizes(&values)
+}
+
+/// `color-<name>` class of each node, on `SCALE` between the extreme values.
+pub fn metric_color_classes(values: &[f64]) -> Vec<Vec<String>> {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|x| {
+            let level = if max > min {
+                ((x - min) / (max - min) * SCALE.len() as f64) as usize
+            } else {
+                SCALE.len() - 1
+            };
+            vec![format!("color-{}", SCALE[level.min(SCALE.len() - 1)])]
+        })
+        .collect()
+}
+
+#[component]
+fn MetricSelect(
+    label: String,
+    value: Option<NodeMetric>,
+    onchange: EventHandler<Option<NodeMetric>>,
+) -> Element {
+    rsx! {
+        div { class: "style-select",
+            span { "{label}" }
+            select {
+                onchange: move |e: Event<FormData>| {
+                    onchange.call(NodeMetric::all().into_iter().find(|m| m.label() == e.value()))
+                },
+                option { value: "", selected: value.is_none(), "none" }
+                optgroup { label: "aggregated graph",
+                    for m in NodeMetric::all().into_iter().filter(|m| m.is_static()) {
+                        option { value: m.label(), selected: value == Some(m), "{m.label()}" }
+                    }
+                }
+                optgroup { label: "link stream",
+                    for m in NodeMetric::all().into_iter().filter(|m| !m.is_static()) {
+                        option { value: m.label(), selected: value == Some(m), "{m.label()}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn AttributeSelect(
+    label: String,
+    options: Vec<String>,
+    value: Option<String>,
+    onchange: EventHandler<Option<String>>,
+) -> Element {
+    rsx! {
+        div { class: "style-select",
+            span { "{label}" }
+            select {
+                onchange: move |e: Event<FormData>| {
+                    let v = e.value();
+                    onchange.call(if v.is_empty() { None } else { Some(v) })
+                },
+                option { value: "", selected: value.is_none(), "none" }
+                for name in options {
+                    option {
+                        selected: value.as_ref() == Some(&name),
+                        value: "{name}",
+                        "{name}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn Legend(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    style: ReadOnlySignal<NodeStyle>,
+) -> Element {
+    let dataset = current_dataset.read();
+    let style = style.read();
+
+    let colors = style
+        .color_by
+        .as_ref()
+        .and_then(|name| dataset.node_attribute(name))
+        .map(categories)
+        .unwrap_or_default();
+    let shapes = style
+        .shape_by
+        .as_ref()
+        .and_then(|name| dataset.node_attribute(name))
+        .map(categories)
+        .unwrap_or_default();
+    let range = style
+        .size_by
+        .as_ref()
+        .and_then(|name| dataset.node_attribute(name))
+        .and_then(|values| {
+            let numbers: Vec<f64> = values.iter().flatten().filter_map(|v| v.as_f64()).collect();
+            // no range without numbers
+            let min = numbers.iter().copied().reduce(f64::min)?;
+            let max = numbers.iter().copied().reduce(f64::max)?;
+            Some((min, max))
+        });
+
+    rsx! {
+        div { class: "legend",
+            for (i , c) in colors.iter().enumerate() {
+                div { class: "legend-item",
+                    svg { width: 12, height: 12,
+                        path {
+                            class: "node color-{COLORS[i % COLORS.len()]}",
+                            d: NodeShape::Ellipse.path(Vec2::new(6., 6.), 5.)
+                        }
+                    }
+                    "{c}"
+                }
+            }
+            for (i , c) in shapes.iter().enumerate() {
+                div { class: "legend-item",
+                    svg { width: 12, height: 12,
+                        path {
+                            class: "node",
+                            d: SHAPES[i % SHAPES.len()].path(Vec2::new(6., 6.), 5.)
+                        }
+                    }
+                    "{c}"
+                }
+            }
+            if let Some((min, max)) = range {
+                div { class: "legend-item",
+                    svg { width: 12, height: 12,
+                        path { class: "node", d: NodeShape::Ellipse.path(Vec2::new(6., 6.), 1.) }
+                    }
+                    "{min}"
+                    svg { width: 12, height: 12,
+                        path { class: "node", d: NodeShape::Ellipse.path(Vec2::new(6., 6.), 5.) }
+                    }
+                    "{max}"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn StyleControl(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    mut style: Signal<NodeStyle>,
+) -> Element {
+    let dataset = current_dataset.read();
+    let (numeric, categorical): (Vec<String>, Vec<String>) = dataset
+        .attribute_names()
+        .map(|x| x.to_string())
+        .partition(|name| dataset.is_numeric_attribute(name));
+
+    let method = style.read().communities;
+    let size_metric = style.read().size_metric;
+    let color_metric = style.read().color_metric;
+
+    rsx! {
+        div { class: "style-select",
+            span { "communities" }
+            select {
+                onchange: move |e: Event<FormData>| {
+                    style.write().communities = CommunityMethod::ALL
+                        .into_iter()
+                        .find(|m| m.label() == e.value())
+                },
+                option { value: "", selected: method.is_none(), "none" }
+                for m in CommunityMethod::ALL {
+                    option { value: m.label(), selected: method == Some(m), "{m.label()}" }
+                }
+            }
+        }
+        if numeric.is_empty() && categorical.is_empty() {
+            div { class: "default-output", "no node attributes" }
+        } else {
+            AttributeSelect {
+                label: "color",
+                options: categorical.clone(),
+                value: style.read().color_by.clone(),
+                onchange: move |v| style.write().color_by = v
+            }
+            AttributeSelect {
+                label: "shape",
+                options: categorical,
+                value: style.read().shape_by.clone(),
+                onchange: move |v| style.write().shape_by = v
+            }
+            AttributeSelect {
+                label: "size",
+                options: numeric,
+                value: style.read().size_by.clone(),
+                onchange: move |v| style.write().size_by = v
+            }
+            Legend { current_dataset, style }
+        }
+        MetricSelect {
+            label: "size metric",
+            value: size_metric,
+            onchange: move |m| style.write().size_metric = m
+        }
+        MetricSelect {
+            label: "color metric",
+            value: color_metric,
+            onchange: move |m| style.write().color_metric = m
+        }
+        if color_metric.is_some() {
+            div { class: "legend",
+                div { class: "legend-item",
+                    "low"
+                    for c in SCALE {
+                        svg { width: 12, height: 12,
+                            path {
+                                class: "node color-{c}",
+                                d: NodeShape::Ellipse.path(Vec2::new(6., 6.), 5.)
+                            }
+                        }
+                    }
+                    "high"
+                }
+            }
+        }
+    }
+}
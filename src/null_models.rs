@@ -0,0 +1,241 @@
+//! Randomized reference models: each one destroys some correlations of a
+//! link stream and keeps others, so that a measure can be compared between
+//! the data and its randomized versions.
+
+use crate::linkstream::{Link, LinkStreamData};
+use fastrand::Rng;
+use std::ops::Range;
+
+/// number of snapshots moved around by `NullModel::SnapshotShuffle`
+const SNAPSHOTS: u64 = 100;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum NullModel {
+    /// permute start times between links; pairs and durations are kept,
+    /// links ending after the window being moved back
+    TimestampShuffle,
+    /// swap endpoints between links; each node keeps its number of links
+    DegreePreserving,
+    /// permute durations between links; pairs and start times are kept
+    DurationShuffle,
+    /// cut the stream into snapshots and permute them; pairs and durations are kept
+    SnapshotShuffle,
+    /// play the stream backwards
+    TimeReversal,
+}
+
+/// Start and end of a link of `duration` starting at `start`, moved back
+/// if needed so that it ends in `window`.
+fn fit(start: u64, duration: u64, window: &Range<u64>) -> (u64, u64) {
+    let start = start
+        .min(window.end.saturating_sub(duration))
+        .max(window.start);
+    (start, (start + duration).min(window.end))
+}
+
+impl NullModel {
+    pub const ALL: [NullModel; 5] = [
+        NullModel::TimestampShuffle,
+        NullModel::DegreePreserving,
+        NullModel::DurationShuffle,
+        NullModel::SnapshotShuffle,
+        NullModel::TimeReversal,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NullModel::TimestampShuffle => "shuffled timestamps",
+            NullModel::DegreePreserving => "shuffled endpoints",
+            NullModel::DurationShuffle => "shuffled durations",
+            NullModel::SnapshotShuffle => "shuffled snapshots",
+            NullModel::TimeReversal => "time reversal",
+        }
+    }
+
+    /// A randomized copy of `data`, the same for the same seed.
+    pub fn generate(&self, data: &LinkStreamData, seed: u64) -> LinkStreamData {
+        let mut rng = Rng::with_seed(seed);
+        let mut links = data.links().to_vec();
+        let window = data.time_window();
+        match self {
+            NullModel::TimestampShuffle => {
+                let mut starts: Vec<u64> = links.iter().map(|l| l.start).collect();
+                rng.shuffle(&mut starts);
+                for (l, start) in links.iter_mut().zip(starts) {
+                    (l.start, l.end) = fit(start, l.end - l.start, &window);
+                }
+            }
+            NullModel::DegreePreserving => {
+                // double edge swaps (a, b), (c, d) -> (a, d), (c, b)
+                let m = links.len();
+                for _ in 0..10 * m {
+                    let (i, j) = (rng.usize(..m), rng.usize(..m));
+                    let (a, b) = (links[i].n1, links[i].n2);
+                    let (c, d) = (links[j].n1, links[j].n2);
+                    if i == j || a == d || c == b {
+                        continue;
+                    }
+                    links[i].n2 = d;
+                    links[j].n2 = b;
+                }
+            }
+            NullModel::DurationShuffle => {
+                let mut durations: Vec<u64> = links.iter().map(|l| l.end - l.start).collect();
+                rng.shuffle(&mut durations);
+                for (l, duration) in links.iter_mut().zip(durations) {
+                    l.end = (l.start + duration).min(window.end);
+                }
+            }
+            NullModel::SnapshotShuffle => {
+                let width = ((window.end - window.start) / SNAPSHOTS).max(1);
+                let count = (window.end - window.start).div_ceil(width);
+                let mut order: Vec<u64> = (0..count).collect();
+                rng.shuffle(&mut order);
+                for l in links.iter_mut().filter(|_| count > 0) {
+                    // a link starting at the end of the window is in the last snapshot
+                    let k = ((l.start - window.start) / width).min(count - 1);
+                    let start =
+                        order[k as usize] * width + window.start + (l.start - window.start) % width;
+                    // links moved into the last, shorter snapshot may be moved back
+                    (l.start, l.end) = fit(start, l.end - l.start, &window);
+                }
+            }
+            NullModel::TimeReversal => {
+                for l in links.iter_mut() {
+                    *l = Link {
+                        start: window.start + window.end - l.end,
+                        end: window.start + window.end - l.start,
+                        ..*l
+                    };
+                }
+            }
+        }
+        data.with_links(links)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_format::TimeUnit;
+
+    fn link(start: u64, end: u64) -> Link {
+        Link {
+            n1: 0,
+            n2: 1,
+            start,
+            end,
+        }
+    }
+
+    /// 40 links between 6 nodes, of various durations, in `0..1000`
+    fn random_data() -> LinkStreamData {
+        let mut rng = Rng::with_seed(1);
+        let links = (0..40)
+            .map(|_| {
+                let n1 = rng.usize(..6);
+                let n2 = (n1 + rng.usize(1..6)) % 6;
+                let start = rng.u64(..1000);
+                let end = (start + rng.u64(..200)).min(1000);
+                Link { n1, n2, start, end }
+            })
+            .collect();
+        let names = (0..6).map(|i| i.to_string()).collect();
+        LinkStreamData::new(names, links, 0..1000, TimeUnit::Steps)
+    }
+
+    fn sorted<T: Ord>(mut values: Vec<T>) -> Vec<T> {
+        values.sort();
+        values
+    }
+
+    fn durations(data: &LinkStreamData) -> Vec<u64> {
+        sorted(data.links().iter().map(|l| l.end - l.start).collect())
+    }
+
+    fn pairs(data: &LinkStreamData) -> Vec<(usize, usize)> {
+        sorted(data.links().iter().map(|l| (l.n1, l.n2)).collect())
+    }
+
+    fn degrees(data: &LinkStreamData) -> Vec<usize> {
+        let mut degrees = vec![0; 6];
+        for l in data.links() {
+            degrees[l.n1] += 1;
+            degrees[l.n2] += 1;
+        }
+        degrees
+    }
+
+    #[test]
+    fn models_depend_on_the_seed_only() {
+        let data = random_data();
+        for model in NullModel::ALL {
+            assert_eq!(
+                model.generate(&data, 3),
+                model.generate(&data, 3),
+                "{model:?}"
+            );
+            assert!(model
+                .generate(&data, 3)
+                .links()
+                .iter()
+                .all(|l| l.start <= l.end && l.end <= 1000));
+        }
+        assert_ne!(
+            NullModel::TimestampShuffle.generate(&data, 3),
+            NullModel::TimestampShuffle.generate(&data, 4)
+        );
+    }
+
+    #[test]
+    fn models_keep_what_they_claim() {
+        let data = random_data();
+        for seed in 0..5 {
+            let shuffled = NullModel::TimestampShuffle.generate(&data, seed);
+            assert_eq!(pairs(&shuffled), pairs(&data));
+            assert_eq!(durations(&shuffled), durations(&data));
+
+            let shuffled = NullModel::DegreePreserving.generate(&data, seed);
+            assert_eq!(degrees(&shuffled), degrees(&data));
+            assert!(shuffled.links().iter().all(|l| l.n1 != l.n2));
+            let times =
+                |d: &LinkStreamData| sorted(d.links().iter().map(|l| (l.start, l.end)).collect());
+            assert_eq!(times(&shuffled), times(&data));
+
+            let shuffled = NullModel::DurationShuffle.generate(&data, seed);
+            for (a, b) in shuffled.links().iter().zip(data.links()) {
+                assert_eq!((a.n1, a.n2, a.start), (b.n1, b.n2, b.start));
+            }
+
+            let shuffled = NullModel::SnapshotShuffle.generate(&data, seed);
+            assert_eq!(pairs(&shuffled), pairs(&data));
+            assert_eq!(durations(&shuffled), durations(&data));
+        }
+        let reversed = NullModel::TimeReversal.generate(&data, 0);
+        assert_eq!(durations(&reversed), durations(&data));
+        assert_eq!(NullModel::TimeReversal.generate(&reversed, 0), data);
+    }
+
+    #[test]
+    fn snapshot_shuffle_keeps_links_at_the_last_instant() {
+        let links = vec![link(0, 10), link(150, 200), link(200, 200)];
+        let data =
+            LinkStreamData::new(vec!["a".into(), "b".into()], links, 0..200, TimeUnit::Steps);
+        for seed in 0..10 {
+            let shuffled = NullModel::SnapshotShuffle.generate(&data, seed);
+            assert_eq!(durations(&shuffled), vec![0, 10, 50]);
+            assert!(shuffled.links().iter().all(|l| l.end <= 200));
+        }
+    }
+
+    #[test]
+    fn snapshot_shuffle_of_an_empty_window() {
+        let data = LinkStreamData::new(
+            vec!["a".into(), "b".into()],
+            vec![link(5, 5)],
+            5..5,
+            TimeUnit::Steps,
+        );
+        assert_eq!(NullModel::SnapshotShuffle.generate(&data, 0), data);
+    }
+}
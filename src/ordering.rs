@@ -0,0 +1,205 @@
+//! Orderings of the nodes, as a list of node indices from top to bottom.
+
+use crate::communities::CommunityMethod;
+use crate::utils::Matrix;
+use crate::LinkStream;
+use std::ops::Range;
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum NodeOrder {
+    #[default]
+    Index,
+    Name,
+    Community,
+    Activity,
+    Degree,
+    /// reverse Cuthill-McKee: brings linked nodes close to each other
+    CuthillMcKee,
+}
+
+impl NodeOrder {
+    pub const ALL: [NodeOrder; 6] = [
+        NodeOrder::Index,
+        NodeOrder::Name,
+        NodeOrder::Community,
+        NodeOrder::Activity,
+        NodeOrder::Degree,
+        NodeOrder::CuthillMcKee,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NodeOrder::Index => "index",
+            NodeOrder::Name => "name",
+            NodeOrder::Community => "community",
+            NodeOrder::Activity => "activity",
+            NodeOrder::Degree => "degree",
+            NodeOrder::CuthillMcKee => "cuthill-mckee",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|o| o.label() == label)
+            .unwrap_or_default()
+    }
+
+    /// Communities are found with `method`.
+    pub fn order(
+        &self,
+        stream: &LinkStream,
+        time_window: Range<u64>,
+        method: CommunityMethod,
+    ) -> Vec<usize> {
+        let n = stream.node_count();
+        let mut nodes: Vec<usize> = (0..n).collect();
+        match self {
+            NodeOrder::Index => {}
+            NodeOrder::Name => {
+                let names: Vec<&str> = stream.node_names().collect();
+                nodes.sort_by_key(|i| names.get(*i).copied().unwrap_or_default());
+            }
+            NodeOrder::Community => {
+                let labels = method.detect(&stream.interaction_matrix(time_window));
+                nodes.sort_by_key(|i| labels[*i]);
+            }
+            NodeOrder::Activity => {
+                let activity = stream.interaction_matrix(time_window).sum_one_level();
+                nodes.sort_by(|a, b| activity[*b].total_cmp(&activity[*a]));
+            }
+            NodeOrder::Degree => {
+                let degrees = degrees(&stream.interaction_matrix(time_window));
+                nodes.sort_by_key(|i| std::cmp::Reverse(degrees[*i]));
+            }
+            NodeOrder::CuthillMcKee => {
+                nodes = reverse_cuthill_mckee(&stream.interaction_matrix(time_window));
+            }
+        }
+        nodes
+    }
+}
+
+/// number of distinct neighbours of each node
+fn degrees(matrix: &[Vec<f64>]) -> Vec<usize> {
+    matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(j, w)| *j != i && **w > 0.)
+                .count()
+        })
+        .collect()
+}
+
+fn reverse_cuthill_mckee(matrix: &[Vec<f64>]) -> Vec<usize> {
+    let n = matrix.len();
+    let degrees = degrees(matrix);
+    let mut visited = vec![false; n];
+    let mut result = Vec::with_capacity(n);
+
+    let mut by_degree: Vec<usize> = (0..n).collect();
+    by_degree.sort_by_key(|i| degrees[*i]);
+
+    // one breadth first search per connected component,
+    // starting from the node of smallest degree
+    for root in by_degree {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let first = result.len();
+        result.push(root);
+        let mut k = first;
+        while k < result.len() {
+            let node = result[k];
+            let mut neighbours: Vec<usize> = (0..n)
+                .filter(|j| !visited[*j] && *j != node && matrix[node][*j] > 0.)
+                .collect();
+            neighbours.sort_by_key(|j| degrees[*j]);
+            for j in neighbours {
+                visited[j] = true;
+                result.push(j);
+            }
+            k += 1;
+        }
+    }
+    result.reverse();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linkstream::test_stream;
+
+    // path c - a - b - d, with decreasing durations along the path
+    fn path() -> LinkStream {
+        test_stream(
+            &["c", "a", "b", "d"],
+            &[(0, 1, 0, 10), (1, 2, 0, 5), (2, 3, 0, 1)],
+            0..10,
+        )
+    }
+
+    #[test]
+    fn simple_orders() {
+        let stream = path();
+        let order = |o: NodeOrder| o.order(&stream, 0..10, CommunityMethod::default());
+        assert_eq!(order(NodeOrder::Index), [0, 1, 2, 3]);
+        assert_eq!(order(NodeOrder::Name), [1, 2, 0, 3]);
+        assert_eq!(order(NodeOrder::Activity), [1, 0, 2, 3]);
+        assert_eq!(order(NodeOrder::Degree), [1, 2, 0, 3]);
+    }
+
+    #[test]
+    fn orders_depend_on_the_window() {
+        let stream = path();
+        let order = NodeOrder::Degree.order(&stream, 6..10, CommunityMethod::default());
+        assert_eq!(order, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn cuthill_mckee_follows_the_path() {
+        let stream = test_stream(
+            &["a", "b", "c", "d", "e"],
+            &[(0, 3, 0, 1), (3, 1, 0, 1), (1, 4, 0, 1), (4, 2, 0, 1)],
+            0..1,
+        );
+        let order = NodeOrder::CuthillMcKee.order(&stream, 0..1, CommunityMethod::default());
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, [0, 1, 2, 3, 4]);
+        // every node is next to its neighbours: the bandwidth is 1
+        let matrix = stream.interaction_matrix(0..1);
+        for pair in order.windows(2) {
+            assert!(matrix[pair[0]][pair[1]] > 0.);
+        }
+    }
+
+    #[test]
+    fn communities_are_contiguous() {
+        // two triangles {0, 2, 4} and {1, 3, 5} joined by a short link
+        let stream = test_stream(
+            &["a", "b", "c", "d", "e", "f"],
+            &[
+                (0, 2, 0, 10),
+                (2, 4, 0, 10),
+                (4, 0, 0, 10),
+                (1, 3, 0, 10),
+                (3, 5, 0, 10),
+                (5, 1, 0, 10),
+                (4, 5, 0, 1),
+            ],
+            0..10,
+        );
+        for method in CommunityMethod::ALL {
+            let order = NodeOrder::Community.order(&stream, 0..10, method);
+            let mut first: Vec<usize> = order[..3].to_vec();
+            first.sort();
+            assert!(first == [0, 2, 4] || first == [1, 3, 5], "{method:?}");
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Contact history of the pair of nodes of a clicked edge.
+
+use crate::linkstream::Link;
+use crate::timeline_brush::clamp_window;
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::ops::Range;
+
+/// rows of the link table
+const MAX_ROWS: usize = 100;
+
+/// Links between `u` and `v` in the whole stream, by start time.
+pub fn pair_links(stream: &LinkStream, u: usize, v: usize) -> Vec<Link> {
+    let mut links: Vec<Link> = stream
+        .links()
+        .iter()
+        .filter(|l| (l.n1, l.n2) == (u, v) || (l.n1, l.n2) == (v, u))
+        .copied()
+        .collect();
+    links.sort_by_key(|l| l.start);
+    links
+}
+
+/// Summary, timeline and table of the links of `pair`, over the whole
+/// stream so that contacts outside of the time window are seen too.
+#[component]
+pub fn PairInspector(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    mut time_window: Signal<Range<u64>>,
+    mut pair: Signal<Option<(usize, usize)>>,
+) -> Element {
+    let links = use_memo(move || {
+        let (u, v) = pair()?;
+        Some(pair_links(&current_dataset.read(), u, v))
+    });
+
+    let (Some((u, v)), Some(links)) = (pair(), links()) else {
+        return rsx! {};
+    };
+    let dataset = current_dataset.read();
+    let format = dataset.time_format();
+    let names: Vec<&str> = dataset.node_names().collect();
+    let bounds = dataset.time_window();
+    let total: u64 = links.iter().map(|l| l.end - l.start).sum();
+    let mean = total / (links.len() as u64).max(1);
+    let span = links
+        .first()
+        .map(|l| l.start)
+        .zip(links.iter().map(|l| l.end).max());
+
+    let x = |t: u64| (t - bounds.start) as f64 / (bounds.end - bounds.start).max(1) as f64 * 100.;
+    let Range { start, end } = time_window();
+
+    rsx! {
+        div { class: "data-output",
+            p { class: "node-name", "{names[u]} – {names[v]}" }
+            p { "{links.len()} links" }
+            p { "total duration: {format.format_duration(total)}" }
+            p { "mean duration: {format.format_duration(mean)}" }
+            if let Some((first, last)) = span {
+                button {
+                    onclick: move |_| {
+                        let bounds = current_dataset.read().time_window();
+                        time_window.set(clamp_window(first as f64..last as f64, bounds))
+                    },
+                    "zoom to their contacts"
+                }
+            }
+            button { onclick: move |_| pair.set(None), "close" }
+        }
+        svg {
+            class: "pair-timeline",
+            preserve_aspect_ratio: "none",
+            view_box: "0 0 100 10",
+            width: "100%",
+            rect {
+                class: "pair-window",
+                x: x(start),
+                y: 0,
+                width: x(end) - x(start),
+                height: 10
+            }
+            for l in links.iter() {
+                rect {
+                    class: "pair-contact",
+                    x: x(l.start),
+                    y: 2,
+                    // instantaneous links stay visible
+                    width: (x(l.end) - x(l.start)).max(0.2),
+                    height: 6
+                }
+            }
+        }
+        table { class: "pair-table",
+            tr {
+                th { "from" }
+                th { "to" }
+                th { "duration" }
+            }
+            for l in links.iter().take(MAX_ROWS) {
+                tr {
+                    td { "{format.format_time(l.start)}" }
+                    td { "{format.format_time(l.end)}" }
+                    td { "{format.format_duration(l.end - l.start)}" }
+                }
+            }
+        }
+        if links.len() > MAX_ROWS {
+            div { class: "default-output", "first {MAX_ROWS} links shown" }
+        }
+    }
+}
@@ -1,17 +1,70 @@
 use dioxus::prelude::*;
 use kurbo::Vec2;
 
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum NodeShape {
+    #[default]
+    Ellipse,
+    Diamond,
+    Hexagon,
+    Rectangle,
+    Triangle,
+}
+
+impl NodeShape {
+    /// svg path of the shape centered on `c`, inscribed in a circle of radius `r`
+    pub fn path(&self, c: Vec2, r: f64) -> String {
+        let turns: Vec<f64> = match self {
+            NodeShape::Ellipse => {
+                return format!(
+                    "M {} {} a {r} {r} 0 1 0 {} 0 a {r} {r} 0 1 0 {} 0",
+                    c.x - r,
+                    c.y,
+                    2. * r,
+                    -2. * r
+                )
+            }
+            NodeShape::Diamond => (0..4).map(|i| i as f64 * 0.25).collect(),
+            NodeShape::Hexagon => (0..6).map(|i| i as f64 / 6.).collect(),
+            NodeShape::Rectangle => (0..4).map(|i| 0.125 + i as f64 * 0.25).collect(),
+            NodeShape::Triangle => (0..3).map(|i| 0.75 + i as f64 / 3.).collect(),
+        };
+        let corners: Vec<Vec2> = turns
+            .into_iter()
+            .map(|turn| c + Vec2::from_angle(turn * std::f64::consts::TAU) * r)
+            .collect();
+
+        let mut d = String::new();
+        for (i, p) in corners.iter().enumerate() {
+            let command = if i == 0 { 'M' } else { 'L' };
+            d.push_str(&format!("{command} {} {} ", p.x, p.y));
+        }
+        d.push('Z');
+        d
+    }
+}
+
 #[derive(Props, Clone, PartialEq, Default, Debug)]
 pub struct GraphProps {
     pub size: usize,
     pub names: Vec<Option<String>>,
     pub node_weights: Vec<f64>,
     pub node_classes: Vec<Vec<String>>,
+    pub node_shapes: Option<Vec<NodeShape>>,
+    /// nodes drawn, all of them by default
+    pub visible: Option<Vec<bool>>,
     pub edges: Vec<(usize, usize)>,
     pub edge_weights: Vec<Vec<f64>>,
     pub positions: Signal<Vec<Vec2>>,
+    pub on_hover: Option<EventHandler<Option<usize>>>,
+    pub on_click: Option<EventHandler<usize>>,
+    pub on_edge_click: Option<EventHandler<(usize, usize)>>,
+    /// drawn above the other edges, whatever their weight
+    pub highlighted_edges: Option<Vec<(usize, usize)>>,
     pub width: Option<i64>,
     pub height: Option<i64>,
+    /// logical size of the drawing, scaled to `width` x `height`
+    pub view_box: Option<(f64, f64)>,
 }
 
 pub fn MyGraph(mut g: GraphProps) -> Element {
@@ -31,23 +84,31 @@ pub fn MyGraph(mut g: GraphProps) -> Element {
         (None, Some(h)) => (h * 2, h),
         (None, None) => (1500, 750),
     };
+    let (view_w, view_h) = g.view_box.unwrap_or((width as f64, height as f64));
+    let scale = width as f64 / view_w;
 
     rsx! {
         svg {
             height,
             width,
-            //view_box: "0 0 {RELATIVE_WIDTH} {RELATIVE_HEIGHT}",
+            view_box: "0 0 {view_w} {view_h}",
             onmouseup: move |_| *selected.write() = None,
             onmousemove: move |e: MouseEvent| {
                 if let Some(id) = selected() {
                     let coord = e.coordinates().element();
-                    g.positions.write()[id] = (coord.x, coord.y).into();
+                    g.positions.write()[id] = (coord.x / scale, coord.y / scale).into();
                 }
             },
             for a in 0..n {
                 for b in 0..=a {
                     if g.edge_weights[a][b] > 0. {
                         line {
+                            class: "edge",
+                            onclick: move |_| {
+                                if let Some(f) = g.on_edge_click {
+                                    f.call((b, a))
+                                }
+                            },
                             stroke: "rgba(0,0,0,{g.edge_weights[a][b]})",
                             stroke_width: "{NODE_SIZE/3.}px",
                             x1: pos[a].x,
@@ -58,13 +119,40 @@ pub fn MyGraph(mut g: GraphProps) -> Element {
                     }
                 }
             }
-            for id in 0..n {
+            for (a , b) in g.highlighted_edges.clone().unwrap_or_default() {
+                line {
+                    class: "highlighted-edge",
+                    x1: pos[a].x,
+                    y1: pos[a].y,
+                    x2: pos[b].x,
+                    y2: pos[b].y
+                }
+            }
+            for id in (0..n).filter(|id| g.visible.as_ref().is_none_or(|v| v[*id])) {
                 // TODO: z-index
-                circle {
+                path {
+                    class: "node {g.node_classes[id].join(\" \")}",
                     onmousedown: move |_| *selected.write() = Some(id),
-                    r: NODE_SIZE * g.node_weights[id],
-                    cx: pos[id].x,
-                    cy: pos[id].y
+                    onclick: move |_| {
+                        if let Some(f) = g.on_click {
+                            f.call(id)
+                        }
+                    },
+                    onmouseenter: move |_| {
+                        if let Some(f) = g.on_hover {
+                            f.call(Some(id))
+                        }
+                    },
+                    onmouseleave: move |_| {
+                        if let Some(f) = g.on_hover {
+                            f.call(None)
+                        }
+                    },
+                    d: g
+                        .node_shapes
+                        .as_ref()
+                        .map_or(NodeShape::Ellipse, |shapes| shapes[id])
+                        .path(pos[id], NODE_SIZE * g.node_weights[id])
                 }
                 if let Some(name) = &g.names[id] {
                     text {
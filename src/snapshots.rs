@@ -0,0 +1,219 @@
+use crate::aggregation::Aggregation;
+use crate::communities;
+use crate::filters::Filter;
+use crate::graph_data::{GraphData, Highlight};
+use crate::node_style::{NodeMetric, NodeStyle};
+use crate::render_graph::NodeShape;
+use crate::temporal_centrality::TemporalValues;
+use crate::utils::{data_url, escape_xml};
+use crate::{GraphView, LinkStream};
+use dioxus::prelude::*;
+use kurbo::Vec2;
+use std::ops::Range;
+
+const COLUMNS: usize = 3;
+const CELL: f64 = 300.;
+/// logical size of a graph drawing, see `force_directed_layout::compute`
+const DRAWING: f64 = 850.;
+
+/// Standalone svg of a grid of graphs, each with its title.
+fn grid_svg(graphs: &[(String, GraphData)], positions: &[Vec2]) -> String {
+    let rows = graphs.len().div_ceil(COLUMNS);
+    let scale = CELL / DRAWING;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"sans-serif\">\n",
+        COLUMNS as f64 * CELL,
+        rows as f64 * (CELL + 20.)
+    );
+    for (k, (title, graph)) in graphs.iter().enumerate() {
+        let x = (k % COLUMNS) as f64 * CELL;
+        let y = (k / COLUMNS) as f64 * (CELL + 20.);
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-size=\"12\">{}</text>\n",
+            x + 4.,
+            y + 14.,
+            escape_xml(title)
+        ));
+        svg.push_str(&format!(
+            "<g transform=\"translate({x} {}) scale({scale})\">\n",
+            y + 20.
+        ));
+        // edges are listed in both directions
+        for (a, b) in graph.edges.iter().filter(|(a, b)| a <= b) {
+            let w = graph.edge_weights[*a][*b];
+            svg.push_str(&format!(
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\" stroke-opacity=\"{w}\" stroke-width=\"3.3\"/>\n",
+                positions[*a].x, positions[*a].y, positions[*b].x, positions[*b].y
+            ));
+        }
+        for (i, p) in positions
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| graph.visible[*i])
+        {
+            let fill = graph.node_classes[i]
+                .iter()
+                .find_map(|c| c.strip_prefix("color-"))
+                .unwrap_or("black");
+            let shape = graph
+                .node_shapes
+                .as_ref()
+                .map_or(NodeShape::Ellipse, |shapes| shapes[i]);
+            svg.push_str(&format!(
+                "<path d=\"{}\" fill=\"{fill}\"/>\n",
+                shape.path(*p, 10. * graph.node_weights[i])
+            ));
+            if let Some(name) = &graph.names[i] {
+                svg.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" font-size=\"10\">{}</text>\n",
+                    p.x + 20.,
+                    p.y + 5.,
+                    escape_xml(name)
+                ));
+            }
+        }
+        svg.push_str("</g>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Grid of graphs aggregated at several times, all with the same positions.
+/// The times are evenly spaced in the time window, unless some were pinned.
+#[component]
+pub fn SmallMultiples(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    time_window: ReadOnlySignal<Range<u64>>,
+    aggregation: ReadOnlySignal<Aggregation>,
+    time: ReadOnlySignal<u64>,
+    positions: Signal<Vec<Vec2>>,
+    node_style: ReadOnlySignal<NodeStyle>,
+    filter: ReadOnlySignal<Filter>,
+    hovered: Signal<Vec<usize>>,
+    highlight: ReadOnlySignal<Highlight>,
+    /// the same for every snapshot, see `NodeStyle::temporal_centralities`
+    temporal_centralities: ReadOnlySignal<TemporalValues>,
+) -> Element {
+    let mut count = use_signal(|| 6u64);
+    let mut pinned: Signal<Vec<u64>> = use_signal(Vec::new);
+
+    let windows = use_memo(move || {
+        let Range { start, end } = time_window();
+        let times: Vec<u64> = if pinned.read().is_empty() {
+            let step = (end - start) / count();
+            (0..count()).map(|k| start + step / 2 + k * step).collect()
+        } else {
+            pinned()
+        };
+        times
+            .into_iter()
+            .map(|t| (t, aggregation().window_at(t, &time_window())))
+            .collect::<Vec<(u64, Range<u64>)>>()
+    });
+
+    // communities tracked from one snapshot to the next
+    let labels = use_memo(move || match node_style.read().communities {
+        Some(method) => {
+            let w: Vec<Range<u64>> = windows.read().iter().map(|(_, w)| w.clone()).collect();
+            communities::temporal_communities(&current_dataset.read(), &w, method)
+                .into_iter()
+                .map(Some)
+                .collect()
+        }
+        None => vec![None; windows.read().len()],
+    });
+
+    // not built again when only the cursor moves
+    let export = use_memo(move || {
+        let dataset = current_dataset.read();
+        let format = dataset.time_format();
+        let style = node_style.read();
+        let temporal = |m: Option<NodeMetric>| match m? {
+            NodeMetric::Temporal(c) => temporal_centralities
+                .read()
+                .iter()
+                .find(|(x, _)| *x == c)
+                .map(|(_, v)| v.clone()),
+            NodeMetric::Static(_) => None,
+        };
+        let (size_values, color_values) =
+            (temporal(style.size_metric), temporal(style.color_metric));
+        let snapshot = |m: Option<NodeMetric>, values: &Option<Vec<f64>>, w: &Range<u64>| {
+            values
+                .clone()
+                .or_else(|| m.map(|m| m.values(&dataset, w.clone())))
+        };
+        let graphs: Vec<(String, GraphData)> = windows
+            .read()
+            .iter()
+            .zip(labels.read().iter())
+            .map(|((t, w), labels)| {
+                let mut graph = GraphData::new(&dataset, time_window(), w.clone(), &style);
+                if let Some(values) = snapshot(style.size_metric, &size_values, w) {
+                    graph.size_by_metric(&values);
+                }
+                if let Some(values) = snapshot(style.color_metric, &color_values, w) {
+                    graph.color_by_metric(&values);
+                }
+                if let Some(labels) = labels {
+                    graph.color_communities(labels);
+                }
+                graph.apply_filter(&dataset, &filter.read());
+                (format.format_time(*t), graph)
+            })
+            .collect();
+        data_url("image/svg+xml", &grid_svg(&graphs, &positions.read()))
+    });
+
+    let format = current_dataset.read().time_format();
+
+    rsx! {
+        div { class: "view-container",
+            div { class: "view-options",
+                span { "snapshots " }
+                select {
+                    disabled: !pinned.read().is_empty(),
+                    onchange: move |e: Event<FormData>| count.set(e.value().parse().unwrap_or(6)),
+                    for k in [2, 3, 4, 6, 9, 12] {
+                        option { value: "{k}", selected: k == count(), "{k}" }
+                    }
+                }
+                button {
+                    onclick: move |_| {
+                        let t = time();
+                        let mut p = pinned.write();
+                        if !p.contains(&t) {
+                            p.push(t);
+                            p.sort();
+                        }
+                    },
+                    "pin current time"
+                }
+                button { onclick: move |_| pinned.write().clear(), "evenly spaced" }
+                a { href: export(), download: "snapshots.svg", "export" }
+            }
+            div { class: "snapshot-grid",
+                for ((t , window) , labels) in windows().into_iter().zip(labels()) {
+                    div { class: "snapshot",
+                        p { "{format.format_time(t)}" }
+                        GraphView {
+                            current_dataset,
+                            time_window,
+                            aggregation_window: window,
+                            positions,
+                            node_style,
+                            filter,
+                            hovered,
+                            highlight,
+                            communities: labels,
+                            temporal_centralities,
+                            width: CELL as i64,
+                            height: CELL as i64,
+                            view_box: (DRAWING, DRAWING)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
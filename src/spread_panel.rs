@@ -0,0 +1,95 @@
+use crate::graph_data::Highlight;
+use crate::temporal_paths::{self, Hop};
+use crate::utils::PlayButton;
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::ops::Range;
+
+/// Earliest time each node can be informed by the source, and the hop that informs it.
+pub type Spread = (Vec<Option<u64>>, Vec<Option<Hop>>);
+
+pub fn spread(stream: &LinkStream, source: usize, start: u64) -> Spread {
+    let end = stream.time_window().end;
+    let adjacency = temporal_paths::Adjacency::new(stream, start..end);
+    temporal_paths::earliest_arrivals(&adjacency, source, start)
+}
+
+/// State of the propagation at time `t`.
+pub fn spread_highlight(source: usize, spread: &Spread, t: u64) -> Highlight {
+    let (arrival, parent) = spread;
+    let mut highlight = Highlight::default();
+    for (i, a) in arrival.iter().enumerate() {
+        let class = match a {
+            _ if i == source => "journey-source",
+            Some(a) if *a <= t => "informed",
+            _ => "uninformed",
+        };
+        highlight.node_classes.push((i, class.to_string()));
+        if let (Some(a), Some(hop)) = (a, parent[i]) {
+            if *a <= t {
+                highlight.edges.push((hop.from, hop.to));
+            }
+        }
+    }
+    highlight
+}
+
+/// Reachability of the time window, influence sets of the hovered node,
+/// and playback of the spread from the clicked node.
+#[component]
+pub fn SpreadPanel(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    time_window: ReadOnlySignal<Range<u64>>,
+    spread_source: ReadOnlySignal<Option<(usize, u64)>>,
+    spread: ReadOnlySignal<Option<Spread>>,
+    hovered: ReadOnlySignal<Vec<usize>>,
+    time: ReadOnlySignal<u64>,
+    r_value: Signal<f64>,
+) -> Element {
+    let reachability =
+        use_memo(move || temporal_paths::reachability(&current_dataset.read(), time_window()));
+
+    let dataset = current_dataset.read();
+    let format = dataset.time_format();
+    let names: Vec<&str> = dataset.node_names().collect();
+    let name = |i: usize| names.get(i).copied().unwrap_or_default().to_string();
+    let reach = reachability.read();
+    let ratio = temporal_paths::reachability_ratio(&reach);
+    let list = |nodes: Vec<usize>| {
+        let listed: Vec<String> = nodes.iter().map(|v| name(*v)).collect();
+        (nodes.len(), listed.join(", "))
+    };
+    let influence = hovered.read().first().map(|&u| {
+        let out = (0..reach.len())
+            .filter(|&v| v != u && reach[u][v])
+            .collect();
+        let inward = (0..reach.len())
+            .filter(|&v| v != u && reach[v][u])
+            .collect();
+        (u, list(out), list(inward))
+    });
+
+    rsx! {
+        div { class: "data-output",
+            p { "reachability: {ratio * 100.:.1}% of pairs" }
+            if let Some((u, (out, reached), (inward, reaching))) = influence {
+                p { "{name(u)} reaches {out} nodes: {reached}" }
+                p { "{name(u)} is reached by {inward} nodes: {reaching}" }
+            }
+        }
+        match (spread_source(), spread()) {
+            (Some((source, start)), Some((arrival, _))) => rsx! {
+                div { class: "data-output",
+                    p { "spread from {name(source)} at {format.format_time(start)}" }
+                    p {
+                        "informed: {arrival.iter().filter(|a| a.is_some_and(|a| a <= time())).count()} / {arrival.len()}"
+                    }
+                    PlayButton { r_value }
+                }
+            },
+            _ => rsx! {
+                div { class: "default-output", "click on a node to start a spread at the current time" }
+            },
+        }
+    }
+}
@@ -1,4 +1,32 @@
+use crate::filters::Filter;
+use crate::time_format::TimeFormat;
+use crate::timeline_brush::{clamp_window, TimelineBrush};
+use crate::utils::Matrix;
+use crate::LinkStream;
 use dioxus::prelude::*;
+use std::ops::Range;
+
+/// Interaction score of `n_bar` consecutive slices of `time_window`, normalized
+/// by the maximum, and whether there is no interaction at all. Only links
+/// between `nodes` are counted.
+pub fn intensities(
+    stream: &LinkStream,
+    time_window: Range<u64>,
+    n_bar: u64,
+    nodes: &[bool],
+) -> (Vec<f64>, bool) {
+    let Range { start, end } = time_window;
+    let dt = (end - start) / n_bar;
+    let mut intensities = Vec::new();
+    for i in 0..n_bar {
+        let time_point = start + i * dt;
+        let intensity = stream.interaction_score_during(time_point..time_point + dt, nodes);
+        intensities.push(intensity)
+    }
+
+    let m = intensities.matrix_max();
+    (intensities.matrix_map(|x| x / m), m == 0.)
+}
 
 #[component]
 fn Bar(size: f64, index: usize, intensity: f64) -> Element {
@@ -36,3 +64,80 @@ pub fn SvgTimeLine(n_bar: usize, intensities: Vec<f64>, empty: bool) -> Element
         }
     }
 }
+
+#[component]
+pub fn TimeAxis(time_window: Range<u64>, format: TimeFormat) -> Element {
+    let Range { start, end } = time_window;
+    let width = end.saturating_sub(start).max(1) as f64;
+    let ticks = format.ticks(start..end, 6);
+    let step = match ticks.as_slice() {
+        [a, b, ..] => b - a,
+        _ => end - start,
+    };
+
+    rsx! {
+        div { class: "time-axis",
+            for t in ticks {
+                span {
+                    class: "tick",
+                    left: "{(t - start) as f64 / width * 100.}%",
+                    "{format.format_tick(t, step)}"
+                }
+            }
+        }
+    }
+}
+
+/// Activity of the whole dataset, with the current window and cursor on top.
+/// A click centers the window on the clicked time, and the window can be
+/// brushed or dragged as on the main timeline.
+#[component]
+pub fn OverviewTimeLine(
+    current_dataset: ReadOnlySignal<LinkStream>,
+    time_window: Signal<Range<u64>>,
+    time: ReadOnlySignal<u64>,
+    r_value: Signal<f64>,
+    filter: ReadOnlySignal<Filter>,
+) -> Element {
+    const N_BAR: u64 = 200;
+    let activity = use_memo(move || {
+        let dataset = current_dataset.read();
+        intensities(
+            &dataset,
+            dataset.time_window(),
+            N_BAR,
+            &filter.read().node_mask(&dataset),
+        )
+    });
+    let (bars, empty) = activity();
+
+    let bounds = current_dataset.read().time_window();
+    let total = (bounds.end - bounds.start).max(1) as f64;
+    let ratio = |t: u64| t.saturating_sub(bounds.start) as f64 / total * 100.;
+    let Range { start, end } = time_window();
+
+    rsx! {
+        div { class: "overview",
+            SvgTimeLine { n_bar: N_BAR as usize, intensities: bars, empty }
+            div {
+                class: "overview-window",
+                left: "{ratio(start)}%",
+                width: "{ratio(end) - ratio(start)}%"
+            }
+            div { class: "overview-cursor", left: "{ratio(time())}%" }
+            TimelineBrush {
+                time_window,
+                current_dataset,
+                view: bounds.clone(),
+                on_click: move |t: u64| {
+                    let bounds = current_dataset.read().time_window();
+                    let Range { start, end } = time_window();
+                    let (t, w) = (t as f64, (end - start) as f64);
+                    let new_window = clamp_window(t - w / 2.0..t + w / 2.0, bounds);
+                    r_value.set((t - new_window.start as f64) / w);
+                    time_window.set(new_window);
+                }
+            }
+        }
+    }
+}
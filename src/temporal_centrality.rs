@@ -0,0 +1,210 @@
+//! Centralities based on time respecting paths.
+
+use crate::temporal_paths::{self, Adjacency, PathKind};
+use crate::utils::Matrix;
+use crate::LinkStream;
+use std::ops::Range;
+
+/// number of departure times averaged by the temporal closeness
+const DEPARTURES: u64 = 10;
+/// number of snapshots multiplied by the temporal Katz centrality
+const SNAPSHOTS: u64 = 20;
+
+/// Values of some temporal centralities, each with its centrality.
+pub type TemporalValues = Vec<(TemporalCentrality, Vec<f64>)>;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TemporalCentrality {
+    /// mean of the inverse latencies to the other nodes
+    Closeness,
+    /// fraction of pairs whose shortest journey goes through the node
+    ShortestBetweenness,
+    /// fraction of pairs whose fastest journey goes through the node
+    FastestBetweenness,
+    /// journeys of any length, the longest being attenuated (Grindrod et al., 2011)
+    Katz,
+}
+
+impl TemporalCentrality {
+    pub const ALL: [TemporalCentrality; 4] = [
+        TemporalCentrality::Closeness,
+        TemporalCentrality::ShortestBetweenness,
+        TemporalCentrality::FastestBetweenness,
+        TemporalCentrality::Katz,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TemporalCentrality::Closeness => "temporal closeness",
+            TemporalCentrality::ShortestBetweenness => "shortest betweenness",
+            TemporalCentrality::FastestBetweenness => "fastest betweenness",
+            TemporalCentrality::Katz => "temporal Katz",
+        }
+    }
+
+    pub fn compute(&self, stream: &LinkStream, time_window: Range<u64>) -> Vec<f64> {
+        match self {
+            TemporalCentrality::Closeness => closeness(stream, time_window),
+            TemporalCentrality::ShortestBetweenness => {
+                betweenness(stream, time_window, PathKind::Shortest)
+            }
+            TemporalCentrality::FastestBetweenness => {
+                betweenness(stream, time_window, PathKind::Fastest)
+            }
+            TemporalCentrality::Katz => katz(stream, time_window),
+        }
+    }
+}
+
+/// Latencies are measured from evenly spaced departure times, and a node
+/// that can not be reached counts as infinitely far.
+pub fn closeness(stream: &LinkStream, time_window: Range<u64>) -> Vec<f64> {
+    let n = stream.node_count();
+    let adjacency = Adjacency::new(stream, time_window.clone());
+    let step = ((time_window.end - time_window.start) / DEPARTURES).max(1);
+    let departures: Vec<u64> = (time_window.start..time_window.end)
+        .step_by(step as usize)
+        .collect();
+    let mut result = vec![0.; n];
+    for departure in departures.iter().copied() {
+        for (u, c) in result.iter_mut().enumerate() {
+            let (arrival, _) = temporal_paths::earliest_arrivals(&adjacency, u, departure);
+            *c += arrival
+                .iter()
+                .enumerate()
+                .filter(|(v, _)| *v != u)
+                .filter_map(|(_, a)| *a)
+                .map(|a| 1. / (1 + a - departure) as f64)
+                .sum::<f64>();
+        }
+    }
+    let count = (departures.len() * n.saturating_sub(1)).max(1) as f64;
+    result.matrix_map(|x| x / count)
+}
+
+/// Approximation of the temporal betweenness: a single optimal journey is
+/// taken for each pair, leaving at the start of the window, where the exact
+/// centrality shares each pair among all its optimal journeys.
+pub fn betweenness(stream: &LinkStream, time_window: Range<u64>, kind: PathKind) -> Vec<f64> {
+    let n = stream.node_count();
+    let mut result = vec![0.; n];
+    for s in 0..n {
+        for journey in temporal_paths::journeys(stream, s, time_window.clone(), kind)
+            .into_iter()
+            .flatten()
+        {
+            // the destination of each hop but the last one
+            for hop in journey.iter().rev().skip(1) {
+                result[hop.to] += 1.;
+            }
+        }
+    }
+    let pairs = (n.saturating_sub(1) * n.saturating_sub(2)).max(1) as f64;
+    result.matrix_map(|x| x / pairs)
+}
+
+/// Solution `x` of `a x = b`, by Gaussian elimination.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Vec<f64> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|i, j| a[*i][col].abs().total_cmp(&a[*j][col].abs()))
+            .unwrap();
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let pivot_row = a[col].clone();
+        for row in col + 1..n {
+            let f = a[row][col] / pivot_row[col];
+            for (x, p) in a[row].iter_mut().zip(&pivot_row).skip(col) {
+                *x -= f * p;
+            }
+            b[row] -= f * b[col];
+        }
+    }
+    let mut x = vec![0.; n];
+    for row in (0..n).rev() {
+        let s: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - s) / a[row][row];
+    }
+    x
+}
+
+/// Broadcast centrality: row sums of the product of `(I - α A_k)^-1`
+/// over consecutive snapshots `A_k` of the window.
+pub fn katz(stream: &LinkStream, time_window: Range<u64>) -> Vec<f64> {
+    let n = stream.node_count();
+    let step = ((time_window.end - time_window.start) / SNAPSHOTS).max(1);
+    let snapshots: Vec<Vec<Vec<f64>>> = (time_window.start..time_window.end)
+        .step_by(step as usize)
+        .map(|t| {
+            stream
+                .interaction_matrix(t..t + step)
+                .matrix_map(|w| if w > 0. { 1. } else { 0. })
+        })
+        .collect();
+    // smaller than the inverse of every spectral radius
+    let max_degree = snapshots
+        .iter()
+        .flat_map(|a| a.iter().map(|row| row.iter().sum::<f64>()))
+        .fold(1., f64::max);
+    let alpha = 0.5 / max_degree;
+
+    // x = Q 1 = (I - α A_1)^-1 ... (I - α A_k)^-1 1, from the last snapshot
+    let mut x = vec![1.; n];
+    for a in snapshots.iter().rev() {
+        let mut m = a.matrix_map(|w| -alpha * w);
+        // self loops are ignored
+        for (i, row) in m.iter_mut().enumerate() {
+            row[i] = 1.;
+        }
+        x = solve(m, x);
+        let max = x.iter().copied().fold(f64::MIN_POSITIVE, f64::max);
+        x = x.matrix_map(|v| v / max);
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linkstream::test_stream;
+
+    fn stream(max_time: u64, links: &[(usize, usize, u64, u64)]) -> LinkStream {
+        test_stream(&["a", "b", "c"], links, 0..max_time)
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn closeness_of_a_path() {
+        // a-b during the whole window, b-c from 5, departures at 0, 1, ..., 9
+        let stream = stream(10, &[(0, 1, 0, 9), (1, 2, 5, 9)]);
+        let result = closeness(&stream, 0..10);
+        // latencies to c: 5, 4, 3, 2, 1 then 0 five times
+        let to_c = 1. / 6. + 1. / 5. + 1. / 4. + 1. / 3. + 1. / 2. + 5.;
+        assert!(close(result[0], (10. + to_c) / 20.));
+        assert!(close(result[1], (10. + to_c) / 20.));
+        // c reaches a and b at the same time
+        assert!(close(result[2], 2. * to_c / 20.));
+    }
+
+    #[test]
+    fn katz_of_a_star() {
+        // one snapshot, α = 1/4: x_a - (x_b + x_c) / 4 = 1 and x_b - x_a / 4 = 1
+        let stream = stream(1, &[(0, 1, 0, 1), (0, 2, 0, 1)]);
+        let result = katz(&stream, 0..1);
+        assert!(close(result[0], 1.));
+        assert!(close(result[1], 5. / 6.));
+        assert!(close(result[2], 5. / 6.));
+    }
+
+    #[test]
+    fn katz_follows_time() {
+        // a reaches c through b, c can not reach a
+        let stream = stream(20, &[(0, 1, 0, 5), (1, 2, 15, 20)]);
+        let result = katz(&stream, 0..20);
+        assert!(result[0] > result[2]);
+    }
+}
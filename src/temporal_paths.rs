@@ -0,0 +1,365 @@
+//! Time respecting paths (journeys) in a link stream.
+//!
+//! A journey is a sequence of hops `(from, to, time)` with non decreasing
+//! times, each hop using a link present at that time. Hops are instantaneous,
+//! so several hops can happen at the same time.
+
+use crate::LinkStream;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::Range;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Hop {
+    pub from: usize,
+    pub to: usize,
+    pub time: u64,
+}
+
+pub type Journey = Vec<Hop>;
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum PathKind {
+    /// earliest arrival
+    #[default]
+    Foremost,
+    /// fewest hops
+    Shortest,
+    /// minimum duration between the first and the last hop
+    Fastest,
+}
+
+impl PathKind {
+    pub const ALL: [PathKind; 3] = [PathKind::Foremost, PathKind::Shortest, PathKind::Fastest];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PathKind::Foremost => "foremost",
+            PathKind::Shortest => "shortest",
+            PathKind::Fastest => "fastest",
+        }
+    }
+}
+
+/// Links present during a time window, clipped to it, by node:
+/// `adjacency[u]` contains `(v, start, end)`.
+pub struct Adjacency(Vec<Vec<(usize, u64, u64)>>);
+
+impl Adjacency {
+    pub fn new(stream: &LinkStream, time_window: Range<u64>) -> Self {
+        let mut adjacency = vec![Vec::new(); stream.node_count()];
+        for link in stream.links_during(time_window.clone()) {
+            if link.n1 == link.n2 {
+                continue;
+            }
+            let start = link.start.max(time_window.start);
+            let end = link.end.min(time_window.end);
+            adjacency[link.n1].push((link.n2, start, end));
+            adjacency[link.n2].push((link.n1, start, end));
+        }
+        Self(adjacency)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn neighbours(&self, u: usize) -> &[(usize, u64, u64)] {
+        &self.0[u]
+    }
+}
+
+fn rebuild(source: usize, target: usize, parent: &[Option<Hop>]) -> Journey {
+    let mut journey = Vec::new();
+    let mut node = target;
+    while node != source {
+        let hop = parent[node].expect("reached node without parent");
+        journey.push(hop);
+        node = hop.from;
+    }
+    journey.reverse();
+    journey
+}
+
+/// Earliest arrival time at each node, leaving `source` at `departure`,
+/// and the last hop used to reach it.
+pub fn earliest_arrivals(
+    adjacency: &Adjacency,
+    source: usize,
+    departure: u64,
+) -> (Vec<Option<u64>>, Vec<Option<Hop>>) {
+    let n = adjacency.node_count();
+    let mut arrival = vec![None; n];
+    let mut parent = vec![None; n];
+    let mut done = vec![false; n];
+    let mut queue = BinaryHeap::new();
+
+    arrival[source] = Some(departure);
+    queue.push(Reverse((departure, source)));
+
+    while let Some(Reverse((t, u))) = queue.pop() {
+        if done[u] {
+            continue;
+        }
+        done[u] = true;
+        for &(v, start, end) in adjacency.neighbours(u) {
+            let time = t.max(start);
+            if time > end || done[v] {
+                continue;
+            }
+            if arrival[v].is_none_or(|a| time < a) {
+                arrival[v] = Some(time);
+                parent[v] = Some(Hop {
+                    from: u,
+                    to: v,
+                    time,
+                });
+                queue.push(Reverse((time, v)));
+            }
+        }
+    }
+    (arrival, parent)
+}
+
+/// Journeys arriving as early as possible, leaving at `time_window.start`.
+pub fn foremost(
+    stream: &LinkStream,
+    source: usize,
+    time_window: Range<u64>,
+) -> Vec<Option<Journey>> {
+    let adjacency = Adjacency::new(stream, time_window.clone());
+    let (arrival, parent) = earliest_arrivals(&adjacency, source, time_window.start);
+    (0..adjacency.node_count())
+        .map(|v| arrival[v].map(|_| rebuild(source, v, &parent)))
+        .collect()
+}
+
+/// Journeys with the fewest hops.
+pub fn shortest(
+    stream: &LinkStream,
+    source: usize,
+    time_window: Range<u64>,
+) -> Vec<Option<Journey>> {
+    let adjacency = Adjacency::new(stream, time_window.clone());
+    let n = adjacency.node_count();
+
+    // arrival[v]: earliest arrival at v with at most k hops.
+    // Arriving earlier never prevents a later hop, so it is enough
+    // to extend the earliest arrivals of the previous step.
+    let mut arrival = vec![None; n];
+    arrival[source] = Some(time_window.start);
+    let mut result: Vec<Option<Journey>> = vec![None; n];
+    result[source] = Some(Vec::new());
+
+    let mut journeys: Vec<Option<Journey>> = result.clone();
+    loop {
+        let mut next_arrival = arrival.clone();
+        let mut next_journeys = journeys.clone();
+        for u in 0..n {
+            let Some(t) = arrival[u] else { continue };
+            for &(v, start, end) in adjacency.neighbours(u) {
+                let time = t.max(start);
+                if time <= end && next_arrival[v].is_none_or(|a| time < a) {
+                    next_arrival[v] = Some(time);
+                    let mut journey = journeys[u].clone().unwrap_or_default();
+                    journey.push(Hop {
+                        from: u,
+                        to: v,
+                        time,
+                    });
+                    next_journeys[v] = Some(journey);
+                }
+            }
+        }
+        if next_arrival == arrival {
+            break;
+        }
+        for v in 0..n {
+            if result[v].is_none() {
+                result[v] = next_journeys[v].clone();
+            }
+        }
+        arrival = next_arrival;
+        journeys = next_journeys;
+    }
+    result
+}
+
+/// Journeys minimizing the time between the first and the last hop.
+///
+/// A fastest journey can leave as late as the bounds of its links allow, so
+/// departure times are tried at the bounds of every link, while a link of
+/// the source is present. Durations are measured from the first hop taken.
+pub fn fastest(
+    stream: &LinkStream,
+    source: usize,
+    time_window: Range<u64>,
+) -> Vec<Option<Journey>> {
+    let adjacency = Adjacency::new(stream, time_window.clone());
+    let n = adjacency.node_count();
+
+    let present = |t: u64| {
+        adjacency
+            .neighbours(source)
+            .iter()
+            .any(|(_, start, end)| (*start..=*end).contains(&t))
+    };
+    let mut departures: Vec<u64> = (0..n)
+        .flat_map(|u| adjacency.neighbours(u))
+        .flat_map(|(_, start, end)| [*start, *end])
+        .filter(|t| present(*t))
+        .collect();
+    departures.sort();
+    departures.dedup();
+
+    let mut result: Vec<Option<Journey>> = vec![None; n];
+    result[source] = Some(Vec::new());
+    let duration = |j: &Journey| match (j.first(), j.last()) {
+        (Some(a), Some(b)) => b.time - a.time,
+        _ => 0,
+    };
+
+    for departure in departures {
+        let (arrival, parent) = earliest_arrivals(&adjacency, source, departure);
+        for v in 0..n {
+            if v == source || arrival[v].is_none() {
+                continue;
+            }
+            let journey = rebuild(source, v, &parent);
+            if result[v]
+                .as_ref()
+                .is_none_or(|best| duration(&journey) < duration(best))
+            {
+                result[v] = Some(journey);
+            }
+        }
+    }
+    result
+}
+
+pub fn journeys(
+    stream: &LinkStream,
+    source: usize,
+    time_window: Range<u64>,
+    kind: PathKind,
+) -> Vec<Option<Journey>> {
+    match kind {
+        PathKind::Foremost => foremost(stream, source, time_window),
+        PathKind::Shortest => shortest(stream, source, time_window),
+        PathKind::Fastest => fastest(stream, source, time_window),
+    }
+}
+
+/// `result[u][v]` tells if a journey leaving `u` during the window reaches `v`.
+/// Row `u` is the influence set of `u`, column `v` the nodes that can reach `v`.
+pub fn reachability(stream: &LinkStream, time_window: Range<u64>) -> Vec<Vec<bool>> {
+    let adjacency = Adjacency::new(stream, time_window.clone());
+    (0..adjacency.node_count())
+        .map(|u| {
+            let (arrival, _) = earliest_arrivals(&adjacency, u, time_window.start);
+            arrival.iter().map(|a| a.is_some()).collect()
+        })
+        .collect()
+}
+
+/// Fraction of ordered pairs of distinct nodes `(u, v)` such that `u` reaches `v`.
+pub fn reachability_ratio(reachability: &[Vec<bool>]) -> f64 {
+    let n = reachability.len();
+    if n < 2 {
+        return 0.;
+    }
+    let pairs = reachability
+        .iter()
+        .enumerate()
+        .map(|(u, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(v, r)| *v != u && **r)
+                .count()
+        })
+        .sum::<usize>();
+    pairs as f64 / (n * (n - 1)) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linkstream::test_stream;
+
+    // s-x during [10,20], x-y during [25,26], y-w during [30,40],
+    // s-v during [0,100] and v-w during [50,60]
+    fn small_stream() -> LinkStream {
+        test_stream(
+            &["s", "x", "y", "w", "v"],
+            &[
+                (0, 4, 0, 100),
+                (0, 1, 10, 20),
+                (1, 2, 25, 26),
+                (2, 3, 30, 40),
+                (4, 3, 50, 60),
+            ],
+            0..100,
+        )
+    }
+
+    fn hops(journey: &Option<Journey>) -> Vec<(usize, usize, u64)> {
+        journey
+            .iter()
+            .flatten()
+            .map(|h| (h.from, h.to, h.time))
+            .collect()
+    }
+
+    #[test]
+    fn foremost_journeys() {
+        let stream = small_stream();
+        let journeys = foremost(&stream, 0, 0..100);
+        assert_eq!(hops(&journeys[0]), vec![]);
+        assert_eq!(hops(&journeys[4]), vec![(0, 4, 0)]);
+        assert_eq!(hops(&journeys[2]), vec![(0, 1, 10), (1, 2, 25)]);
+        assert_eq!(hops(&journeys[3]), vec![(0, 1, 10), (1, 2, 25), (2, 3, 30)]);
+        // leaving too late for x
+        assert!(foremost(&stream, 0, 21..100)[1].is_none());
+    }
+
+    #[test]
+    fn shortest_journeys() {
+        let stream = small_stream();
+        let journeys = shortest(&stream, 0, 0..100);
+        assert_eq!(hops(&journeys[3]), vec![(0, 4, 0), (4, 3, 50)]);
+        assert_eq!(hops(&journeys[2]), vec![(0, 1, 10), (1, 2, 25)]);
+    }
+
+    #[test]
+    fn fastest_journeys() {
+        let stream = small_stream();
+        let journeys = fastest(&stream, 0, 0..100);
+        // leaving in the middle of the s-v link
+        assert_eq!(hops(&journeys[3]), vec![(0, 4, 50), (4, 3, 50)]);
+        assert_eq!(hops(&journeys[2]), vec![(0, 1, 20), (1, 2, 25)]);
+        assert_eq!(hops(&journeys[1]).len(), 1);
+    }
+
+    #[test]
+    fn reachability_follows_time() {
+        // a-b during [0,1], then b-c during [5,6]: a reaches c, but c does not reach a
+        let stream = test_stream(&["a", "b", "c"], &[(0, 1, 0, 1), (1, 2, 5, 6)], 0..10);
+        let reach = reachability(&stream, 0..10);
+        assert_eq!(reach[0], [true, true, true]);
+        assert_eq!(reach[1], [true, true, true]);
+        assert_eq!(reach[2], [false, true, true]);
+        assert!((reachability_ratio(&reach) - 5. / 6.).abs() < 1e-9);
+
+        // once a-b is over, a is isolated
+        let reach = reachability(&stream, 2..10);
+        assert_eq!(reach[0], [true, false, false]);
+        assert_eq!(reach[1], [false, true, true]);
+        assert!((reachability_ratio(&reach) - 2. / 6.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reachability_ratio_of_tiny_streams() {
+        assert_eq!(reachability_ratio(&[]), 0.);
+        assert_eq!(reachability_ratio(&[vec![true]]), 0.);
+    }
+}
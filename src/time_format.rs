@@ -0,0 +1,259 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeUnit {
+    #[default]
+    Seconds,
+    Milliseconds,
+    /// abstract time steps, without any calendar meaning
+    Steps,
+}
+
+/// How raw time values of a dataset are displayed.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TimeFormat {
+    pub unit: TimeUnit,
+    /// unix time (in seconds) of the raw time value 0
+    pub epoch: i64,
+    /// minutes east of UTC
+    pub utc_offset: i32,
+}
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+// candidate distances between two ticks, in seconds
+const NICE_STEPS: [u64; 22] = [
+    1,
+    2,
+    5,
+    10,
+    15,
+    30,
+    MINUTE,
+    2 * MINUTE,
+    5 * MINUTE,
+    10 * MINUTE,
+    15 * MINUTE,
+    30 * MINUTE,
+    HOUR,
+    2 * HOUR,
+    3 * HOUR,
+    6 * HOUR,
+    12 * HOUR,
+    DAY,
+    2 * DAY,
+    7 * DAY,
+    30 * DAY,
+    365 * DAY,
+];
+
+/// (year, month, day) of a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
+
+impl TimeFormat {
+    /// number of raw time units in a second
+    fn per_second(&self) -> u64 {
+        match self.unit {
+            TimeUnit::Seconds | TimeUnit::Steps => 1,
+            TimeUnit::Milliseconds => 1000,
+        }
+    }
+
+    /// local time in milliseconds since the unix epoch
+    fn local_millis(&self, t: u64) -> i64 {
+        let millis = match self.unit {
+            TimeUnit::Milliseconds => t as i64,
+            _ => t as i64 * 1000,
+        };
+        millis + (self.epoch + self.utc_offset as i64 * 60) * 1000
+    }
+
+    fn format_with(&self, t: u64, date: bool, seconds: bool, millis: bool) -> String {
+        if self.unit == TimeUnit::Steps {
+            return format!("t={t}");
+        }
+        let local = self.local_millis(t);
+        let s = local.div_euclid(1000).rem_euclid(DAY as i64);
+        let (hh, mm, ss) = (s / 3600, s / 60 % 60, s % 60);
+
+        let mut result = String::new();
+        if date {
+            result.push_str(&format!("{} ", self.format_date(t)));
+        }
+        result.push_str(&format!("{hh:02}:{mm:02}"));
+        if seconds {
+            result.push_str(&format!(":{ss:02}"));
+        }
+        if millis {
+            result.push_str(&format!(".{:03}", local.rem_euclid(1000)));
+        }
+        result.trim().to_string()
+    }
+
+    /// date only, like `1990-09-08`
+    fn format_date(&self, t: u64) -> String {
+        if self.unit == TimeUnit::Steps {
+            return format!("t={t}");
+        }
+        let days = self.local_millis(t).div_euclid(DAY as i64 * 1000);
+        let (y, m, d) = civil_from_days(days);
+        format!("{y:04}-{m:02}-{d:02}")
+    }
+
+    /// full date and time, like `1990-09-08 14:33:12`
+    pub fn format_time(&self, t: u64) -> String {
+        self.format_with(t, true, true, self.unit == TimeUnit::Milliseconds)
+    }
+
+    /// human readable duration keeping the two largest units, like `2h 15m`
+    pub fn format_duration(&self, d: u64) -> String {
+        if self.unit == TimeUnit::Steps {
+            return format!("{d} steps");
+        }
+        let millis = d * 1000 / self.per_second();
+        let units = [
+            (DAY * 1000, "d"),
+            (HOUR * 1000, "h"),
+            (MINUTE * 1000, "m"),
+            (1000, "s"),
+            (1, "ms"),
+        ];
+        let mut rest = millis;
+        let mut parts = Vec::new();
+        for (size, name) in units {
+            if rest >= size && parts.len() < 2 {
+                parts.push(format!("{}{name}", rest / size));
+                rest %= size;
+            } else if !parts.is_empty() {
+                break;
+            }
+        }
+        if parts.is_empty() {
+            "0s".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+
+    /// Increasing round durations (1s, 2s, 5s, 10s, 15s, 30s, 1m...), in raw time units.
+    pub fn nice_durations(&self) -> Box<dyn Iterator<Item = u64>> {
+        match self.unit {
+            TimeUnit::Steps => Box::new((0..19).flat_map(|k| [1, 2, 5].map(|x| x * 10u64.pow(k)))),
+            TimeUnit::Seconds => Box::new(NICE_STEPS.into_iter()),
+            TimeUnit::Milliseconds => Box::new(
+                [1, 2, 5, 10, 20, 50, 100, 200, 500]
+                    .into_iter()
+                    .chain(NICE_STEPS.into_iter().map(|s| s * 1000)),
+            ),
+        }
+    }
+
+    /// Regularly spaced round times inside `window`, with at most `max_ticks` values.
+    pub fn ticks(&self, window: Range<u64>, max_ticks: u64) -> Vec<u64> {
+        let width = window.end.saturating_sub(window.start);
+        if width == 0 || max_ticks == 0 {
+            return Vec::new();
+        }
+        let step = self
+            .nice_durations()
+            .find(|s| width / s < max_ticks)
+            .unwrap_or(width);
+
+        // align ticks on local round times (midnight, full hours...)
+        let shift = match self.unit {
+            TimeUnit::Steps => 0,
+            _ => {
+                let offset = (self.epoch + self.utc_offset as i64 * 60) * self.per_second() as i64;
+                offset.rem_euclid(step as i64) as u64
+            }
+        };
+        let first = (window.start + shift).div_ceil(step) * step - shift;
+        (0..)
+            .map(|i| first + i * step)
+            .take_while(|t| *t <= window.end)
+            .collect()
+    }
+
+    /// label of a tick, precise enough for the spacing of `ticks`
+    pub fn format_tick(&self, t: u64, step: u64) -> String {
+        let step_seconds = step / self.per_second();
+        match self.unit {
+            TimeUnit::Steps => format!("{t}"),
+            _ if step_seconds >= DAY => self.format_date(t),
+            _ if step_seconds >= MINUTE => self.format_with(t, false, false, false),
+            _ => self.format_with(t, false, true, step_seconds == 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2000-01-01, in days since 1970-01-01
+    const Y2K: i64 = 10957;
+
+    #[test]
+    fn civil_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(31 + 28), (1970, 3, 1));
+        assert_eq!(civil_from_days(Y2K - 1), (1999, 12, 31));
+        assert_eq!(civil_from_days(Y2K), (2000, 1, 1));
+        // 2000 is a leap year
+        assert_eq!(civil_from_days(Y2K + 31 + 28), (2000, 2, 29));
+        assert_eq!(civil_from_days(Y2K + 366), (2001, 1, 1));
+    }
+
+    fn day_ticks(format: &TimeFormat, first_day: i64) -> Vec<String> {
+        // from noon of the first day to noon four days later
+        let start = (first_day * DAY as i64 + 12 * HOUR as i64 - format.epoch) as u64;
+        let ticks = format.ticks(start..start + 4 * DAY, 5);
+        assert!(ticks.windows(2).all(|w| w[1] - w[0] == DAY));
+        ticks.iter().map(|t| format.format_tick(*t, DAY)).collect()
+    }
+
+    #[test]
+    fn ticks_across_months_and_years() {
+        let format = TimeFormat::default();
+        assert_eq!(
+            day_ticks(&format, Y2K + 31 + 26),
+            vec!["2000-02-28", "2000-02-29", "2000-03-01", "2000-03-02"]
+        );
+        assert_eq!(
+            day_ticks(&format, Y2K - 2),
+            vec!["1999-12-31", "2000-01-01", "2000-01-02", "2000-01-03"]
+        );
+    }
+
+    #[test]
+    fn ticks_at_local_midnight() {
+        let format = TimeFormat {
+            epoch: 946684800,
+            utc_offset: 60,
+            ..Default::default()
+        };
+        let start = (Y2K * DAY as i64 - format.epoch) as u64;
+        let ticks = format.ticks(start..start + 3 * DAY, 5);
+        // one hour before midnight in UTC
+        assert_eq!(ticks[0] + HOUR, start + DAY);
+        assert_eq!(format.format_tick(ticks[0], DAY), "2000-01-02");
+        assert_eq!(format.format_time(ticks[0]), "2000-01-02 00:00:00");
+    }
+}
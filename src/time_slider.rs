@@ -1,8 +1,12 @@
-use dioxus::prelude::*;
-use std::ops::Range;
+use crate::aggregation::Aggregation;
+use crate::components::ComponentTimeLine;
+use crate::filters::Filter;
+use crate::svg_timeline::{intensities, OverviewTimeLine, TimeAxis};
+use crate::timeline_brush::TimelineBrush;
 use crate::LinkStream;
-use crate::utils::Matrix;
 use crate::SvgTimeLine;
+use dioxus::prelude::*;
+use std::ops::Range;
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Direction {
@@ -39,19 +43,35 @@ pub fn Arrow(direction: Direction, onclick: EventHandler<MouseEvent>) -> Element
     }
 }
 
+/// Zoom level of the slider showing `window`, from 0 (the whole dataset) to 3.
+fn window_zoom(window: &Range<u64>, dataset_window: &Range<u64>) -> f64 {
+    let dataset_w = (dataset_window.end - dataset_window.start) as f64;
+    let w = (window.end - window.start).max(1) as f64;
+    (dataset_w / w).log10().clamp(0., 3.)
+}
+
 #[component]
 pub fn TimeSlider(
     time_window: Signal<Range<u64>>,
     current_dataset: ReadOnlySignal<LinkStream>,
-    zoom: ReadOnlySignal<f64>,
+    mut zoom: Signal<f64>,
     r_value: Signal<f64>,
     time: ReadOnlySignal<u64>,
+    /// times to point out on the timeline
+    marks: ReadOnlySignal<Vec<u64>>,
+    aggregation: ReadOnlySignal<Aggregation>,
+    filter: ReadOnlySignal<Filter>,
 ) -> Element {
     // ne change que quand le zoom a lieu.
     use_effect(move || {
         let dataset_window = current_dataset.read().time_window();
         let dataset_w = (dataset_window.end - dataset_window.start) as f64;
         let new_w = dataset_w * (10f64).powf(-zoom());
+        // the zoom was set from the window, see below: windows narrower
+        // than the slider allows are not widened
+        if (window_zoom(&time_window.peek(), &dataset_window) - zoom()).abs() < 1e-3 {
+            return;
+        }
 
         let t = *time.peek() as f64;
         let mut new_start: f64 = t - new_w / 2.;
@@ -72,21 +92,24 @@ pub fn TimeSlider(
         r_value.set(new_v);
     });
 
-    let mut intensities = Vec::new();
+    // windows selected on the timelines also change the zoom
+    use_effect(move || {
+        let new_zoom = window_zoom(&time_window(), &current_dataset.peek().time_window());
+        if (new_zoom - *zoom.peek()).abs() > 1e-3 {
+            zoom.set(new_zoom);
+        }
+    });
 
     let Range { start, end } = time_window();
-    let dt = (end - start) / 100;
-    for i in 0..100 {
-        let time_point = start + i * dt;
-        let intensity = current_dataset
-            .read()
-            .interaction_score_during(time_point as u64..time_point as u64 + dt);
-        intensities.push(intensity)
-    }
-
-    let m = intensities.matrix_max();
-    let intensities = intensities.matrix_map(|x| x / m);
-    let empty = m == 0.;
+    let (intensities, empty) = {
+        let dataset = current_dataset.read();
+        intensities(
+            &dataset,
+            start..end,
+            100,
+            &filter.read().node_mask(&dataset),
+        )
+    };
 
     let mut translate_window = move |p: f64| {
         let dataset_window = current_dataset.read().time_window();
@@ -125,7 +148,17 @@ pub fn TimeSlider(
 
             div { class: "svg-container",
                 SvgTimeLine { n_bar: 100, intensities, empty }
+                for t in marks().into_iter().filter(|t| (start..end).contains(t)) {
+                    div {
+                        class: "timeline-mark",
+                        left: "{(t - start) as f64 / (end - start) as f64 * 100.}%"
+                    }
+                }
+                TimelineBrush { time_window, current_dataset }
             }
+            ComponentTimeLine { current_dataset, time_window, aggregation }
+            TimeAxis { time_window: start..end, format: current_dataset.read().time_format() }
+            OverviewTimeLine { current_dataset, time_window, time, r_value, filter }
 
             Arrow { onclick: move |_| translate_window(0.1), direction: Direction::Right }
         }
@@ -0,0 +1,160 @@
+use crate::LinkStream;
+use dioxus::prelude::*;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// windows smaller than this would make the timeline bars empty
+pub const MIN_WINDOW_WIDTH: u64 = 100;
+
+/// Fit `window` inside `bounds`, keeping its width when possible.
+pub fn clamp_window(window: Range<f64>, bounds: Range<u64>) -> Range<u64> {
+    let bounds_w = (bounds.end - bounds.start) as f64;
+    let w = (window.end - window.start)
+        .max(MIN_WINDOW_WIDTH as f64)
+        .min(bounds_w);
+    let mut start = window.start;
+    if start < bounds.start as f64 {
+        start = bounds.start as f64;
+    }
+    if start + w > bounds.end as f64 {
+        start = bounds.end as f64 - w;
+    }
+    start as u64..(start + w) as u64
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Drag {
+    /// selecting a new window, from the first position of the mouse
+    Brush(f64),
+    /// moving the current window, from the first position and window start
+    Pan(f64, u64),
+    /// moving the timeline under the mouse, so the window the other way
+    Grab(f64, u64),
+}
+
+/// Transparent layer over an activity timeline:
+/// - drag to select a new time window
+/// - drag the current window, when it is only part of the timeline, to move it
+/// - shift + drag to move the timeline
+/// - wheel to zoom around the mouse
+/// - double click to show the whole dataset
+#[component]
+pub fn TimelineBrush(
+    time_window: Signal<Range<u64>>,
+    current_dataset: ReadOnlySignal<LinkStream>,
+    /// times covered by the timeline, the time window by default
+    view: Option<Range<u64>>,
+    /// called with the time of a click that selected nothing
+    on_click: Option<EventHandler<u64>>,
+) -> Element {
+    let mut element: Signal<Option<Rc<MountedData>>> = use_signal(|| None);
+    let mut width = use_signal(|| 1.);
+    let mut drag: Signal<Option<Drag>> = use_signal(|| None);
+    let mut position = use_signal(|| 0.);
+
+    let measure = move || {
+        if let Some(e) = element() {
+            spawn(async move {
+                if let Ok(rect) = e.get_client_rect().await {
+                    width.set(rect.width().max(1.));
+                }
+            });
+        }
+    };
+
+    // position of the mouse, as a fraction of the timeline
+    let ratio = move |e: &MouseEvent| (e.element_coordinates().x / width()).clamp(0., 1.);
+
+    let window = time_window();
+    let view = view.unwrap_or(window.clone());
+    let view_w = (view.end - view.start).max(1) as f64;
+    let time_at = move |r: f64| view.start as f64 + r * view_w;
+    // the window can only be moved when it does not fill the timeline
+    let movable = window.start > view.start || window.end < view.end;
+    let inside = move |t: f64| movable && (window.start as f64..=window.end as f64).contains(&t);
+
+    let selection = match drag() {
+        Some(Drag::Brush(from)) => {
+            let (a, b) = (from.min(position()), from.max(position()));
+            Some((a * 100., (b - a) * 100.))
+        }
+        _ => None,
+    };
+
+    rsx! {
+        div {
+            class: "timeline-brush",
+            title: if movable {
+                "drag: select a window, drag the window or shift+drag: move it, wheel: zoom, double click: reset"
+            } else {
+                "drag: select a window, shift+drag: move it, wheel: zoom, double click: reset"
+            },
+            prevent_default: "onwheel",
+            onmounted: move |e| {
+                element.set(Some(e.data()));
+                measure();
+            },
+            onmousedown: move |e| {
+                measure();
+                let r = ratio(&e);
+                position.set(r);
+                if inside(time_at(r)) {
+                    drag.set(Some(Drag::Pan(r, time_window().start)));
+                } else if e.modifiers().shift() {
+                    drag.set(Some(Drag::Grab(r, time_window().start)));
+                } else {
+                    drag.set(Some(Drag::Brush(r)));
+                }
+            },
+            onmousemove: move |e| {
+                let r = ratio(&e);
+                position.set(r);
+                let moved = match drag() {
+                    Some(Drag::Pan(from, start)) => Some(start as f64 + (r - from) * view_w),
+                    Some(Drag::Grab(from, start)) => Some(start as f64 - (r - from) * view_w),
+                    _ => None,
+                };
+                if let Some(new_start) = moved {
+                    let Range { start: s, end } = time_window();
+                    let w = (end - s) as f64;
+                    let bounds = current_dataset.read().time_window();
+                    time_window.set(clamp_window(new_start..new_start + w, bounds));
+                }
+            },
+            onmouseup: move |e| {
+                if let Some(Drag::Brush(from)) = drag() {
+                    let r = ratio(&e);
+                    let (a, b) = (time_at(from.min(r)), time_at(from.max(r)));
+                    // a simple click is not a selection
+                    if (r - from).abs() > 0.01 {
+                        let bounds = current_dataset.read().time_window();
+                        time_window.set(clamp_window(a..b, bounds));
+                    } else if let Some(f) = on_click {
+                        f.call(time_at(r) as u64)
+                    }
+                }
+                drag.set(None);
+            },
+            onmouseleave: move |_| drag.set(None),
+            ondoubleclick: move |_| time_window.set(current_dataset.read().time_window()),
+            onwheel: move |e| {
+                let dy = e.delta().strip_units().y;
+                if dy == 0. {
+                    return;
+                }
+                let factor = if dy > 0. { 1.2 } else { 1. / 1.2 };
+                let t = time_at(position());
+                let Range { start, end } = time_window();
+                // the time under the mouse keeps its place in the window
+                let r = ((t - start as f64) / (end - start).max(1) as f64).clamp(0., 1.);
+                let new_w = (end - start) as f64 * factor;
+                let new_start = t - r * new_w;
+                let bounds = current_dataset.read().time_window();
+                time_window.set(clamp_window(new_start..new_start + new_w, bounds));
+            },
+            if let Some((x, w)) = selection {
+                div { class: "brush-selection", left: "{x}%", width: "{w}%" }
+            }
+        }
+    }
+}
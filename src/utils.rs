@@ -1,4 +1,6 @@
+use async_std::task::sleep;
 use dioxus::prelude::*;
+use std::time::Duration;
 use uuid::Uuid;
 
 pub trait Coeff: Copy + std::cmp::PartialOrd + core::iter::Sum {}
@@ -22,16 +24,16 @@ where
     type Item = T;
     type Row = T;
     fn matrix_map(&self, f: impl Fn(T) -> T + Clone) -> Self {
-        self.into_iter().map(|x| f(*x)).collect()
+        self.iter().map(|x| f(*x)).collect()
     }
     fn matrix_max(&self) -> T {
         *self
-            .into_iter()
+            .iter()
             .max_by(|a, b| T::partial_cmp(a, b).unwrap())
             .unwrap()
     }
     fn sum_one_level(&self) -> T {
-        self.into_iter().map(|x| *x).sum()
+        self.iter().copied().sum()
     }
 }
 
@@ -42,23 +44,22 @@ where
     type Item = T;
     type Row = Vec<T>;
     fn matrix_map(&self, f: impl Fn(T) -> T + Clone) -> Self {
-        self.into_iter().map(|x| x.matrix_map(f.clone())).collect()
+        self.iter().map(|x| x.matrix_map(f.clone())).collect()
     }
     fn matrix_max(&self) -> Self::Item {
-        self.into_iter()
+        self.iter()
             .map(|x| x.matrix_max())
             .max_by(|a, b| Self::Item::partial_cmp(a, b).unwrap())
             .unwrap()
     }
     fn sum_one_level(&self) -> Self::Row {
-        self.into_iter().map(|x| x.sum_one_level()).collect()
+        self.iter().map(|x| x.sum_one_level()).collect()
     }
 }
 
-
 #[component]
 pub fn Reset(children: Element) -> Element {
-    rsx!{{std::iter::once(
+    rsx! {{std::iter::once(
         rsx! {
             div {
                 key: "{Uuid::new_v4()}",
@@ -68,3 +69,70 @@ pub fn Reset(children: Element) -> Element {
     )
     }}
 }
+
+/// Button moving the cursor of the time window to its end, step by step.
+#[component]
+pub fn PlayButton(r_value: Signal<f64>) -> Element {
+    let mut playing = use_signal(|| false);
+    let play = move |_| async move {
+        if playing() {
+            playing.set(false);
+            return;
+        }
+        playing.set(true);
+        while playing() && r_value() < 1. {
+            r_value.set((r_value() + 0.005).min(1.));
+            sleep(Duration::from_millis(50)).await;
+        }
+        playing.set(false);
+    };
+    rsx! {
+        button { onclick: play, if playing() { "pause" } else { "play" } }
+    }
+}
+
+/// Text with the characters that have a meaning in xml replaced by entities.
+pub fn escape_xml(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '&' => result.push_str("&amp;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// `data:` url of a text file, to be used as the `href` of a download link.
+pub fn data_url(mime: &str, content: &str) -> String {
+    let mut url = format!("data:{mime};charset=utf-8,");
+    for c in content.chars() {
+        match c {
+            'a'..='z'
+            | 'A'..='Z'
+            | '0'..='9'
+            | '-'
+            | '_'
+            | '.'
+            | '~'
+            | ' '
+            | '/'
+            | ':'
+            | '='
+            | ','
+            | '('
+            | ')' => url.push(c),
+            _ => {
+                let mut buf = [0; 4];
+                for b in c.encode_utf8(&mut buf).bytes() {
+                    url.push_str(&format!("%{b:02X}"));
+                }
+            }
+        }
+    }
+    url
+}